@@ -0,0 +1,59 @@
+#!/usr/bin/env bash
+# Pin the SHA-256 of every prebuilt release archive in the tool catalog.
+#
+# Run after adding a tool release or bumping its version. Each archive is downloaded
+# and its checksum written (or replaced) under its [tools.release.targets.*] table.
+# A URL that does not exist fails the script, so nothing is pinned for it.
+#
+# Usage: scripts/pin-release-checksums.sh [catalog.toml]
+
+set -euo pipefail
+
+catalog="${1:-$(dirname "$0")/../src/tools/catalog.toml}"
+pinned="$(mktemp)"
+trap 'rm -f "$pinned"' EXIT
+
+sha256() {
+    if command -v sha256sum >/dev/null; then sha256sum; else shasum -a 256; fi | cut -d' ' -f1
+}
+
+unquote() {
+    local value="${1#*= \"}"
+    echo "${value%\"}"
+}
+
+version="" url="" key="" target=""
+
+# Write the checksum of the archive of the target table just read
+pin() {
+    [[ -n "$key" ]] || return 0
+
+    local asset="${url//\{version\}/$version}"
+    asset="${asset//\{target\}/${target:-$key}}"
+    echo "$key: $asset" >&2
+    local checksum
+    checksum="$(curl -fsSL "$asset" | sha256)"
+    echo "sha256 = \"$checksum\"" >> "$pinned"
+    key="" target=""
+}
+
+while IFS= read -r line; do
+    case "$line" in
+        "[tools.release.targets."*)
+            pin
+            key="${line#\[tools.release.targets.}"
+            key="${key%\]}"
+            ;;
+        "["* | "")
+            pin
+            ;;
+        "version = "*) version="$(unquote "$line")" ;;
+        "url = "*) url="$(unquote "$line")" ;;
+        "target = "*) target="$(unquote "$line")" ;;
+        "sha256 = "*) continue ;;
+    esac
+    echo "$line" >> "$pinned"
+done < "$catalog"
+pin
+
+cp "$pinned" "$catalog"
@@ -3,8 +3,10 @@ use clap::{Args, Subcommand};
 use colored::Colorize;
 use std::path::{Path, PathBuf};
 
+use crate::templates::context::ProjectContext;
 use crate::templates::registry::TemplateRegistry;
-use crate::utils::fs::ensure_directory;
+use crate::templates::renderer::TemplateRenderer;
+use crate::utils::fs::{ensure_directory, read_file, set_executable};
 
 /// Add components (agents, commands, hooks) to the project
 #[derive(Args)]
@@ -96,18 +98,19 @@ impl AddCommand {
 
         // Load template
         let registry = TemplateRegistry::new();
-        let content = if let Some(template_path) = template {
-            std::fs::read_to_string(template_path)
-                .context("Failed to read template file")?
+        let (content, source) = if let Some(template_path) = template {
+            (read_file(template_path)?, template_path.display().to_string())
         } else {
-            registry.get_default_agent_template(name)?
+            let template = registry.get_default_agent_template(name)?;
+            (template.content, template.layer.to_string())
         };
+        let content = self.render(name, &content)?;
 
         // Write agent file
         std::fs::write(&agent_file, content)
             .context("Failed to write agent file")?;
 
-        println!("✓ Agent created: {}", agent_file.display().to_string().bright_yellow());
+        println!("✓ Agent created: {} {}", agent_file.display().to_string().bright_yellow(), format!("(from {})", source).dimmed());
 
         if edit {
             self.open_in_editor(&agent_file)?;
@@ -135,18 +138,19 @@ impl AddCommand {
 
         // Load template
         let registry = TemplateRegistry::new();
-        let content = if let Some(template_path) = template {
-            std::fs::read_to_string(template_path)
-                .context("Failed to read template file")?
+        let (content, source) = if let Some(template_path) = template {
+            (read_file(template_path)?, template_path.display().to_string())
         } else {
-            registry.get_default_command_template(name)?
+            let template = registry.get_default_command_template(name)?;
+            (template.content, template.layer.to_string())
         };
+        let content = self.render(name, &content)?;
 
         // Write command file
         std::fs::write(&command_file, content)
             .context("Failed to write command file")?;
 
-        println!("✓ Command created: {}", command_file.display().to_string().bright_yellow());
+        println!("✓ Command created: {} {}", command_file.display().to_string().bright_yellow(), format!("(from {})", source).dimmed());
 
         if edit {
             self.open_in_editor(&command_file)?;
@@ -174,27 +178,22 @@ impl AddCommand {
 
         // Load template
         let registry = TemplateRegistry::new();
-        let content = if let Some(template_path) = template {
-            std::fs::read_to_string(template_path)
-                .context("Failed to read template file")?
+        let (content, source) = if let Some(template_path) = template {
+            (read_file(template_path)?, template_path.display().to_string())
         } else {
-            registry.get_default_hook_template(name, event)?
+            let template = registry.get_default_hook_template(name, event)?;
+            (template.content, template.layer.to_string())
         };
+        // Scripts are copied as is: shell syntax such as `${#args[@]}` is not a template
 
         // Write hook file
         std::fs::write(&hook_file, content)
             .context("Failed to write hook file")?;
 
         // Make executable on Unix systems
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = std::fs::metadata(&hook_file)?.permissions();
-            perms.set_mode(0o755);
-            std::fs::set_permissions(&hook_file, perms)?;
-        }
+        set_executable(&hook_file)?;
 
-        println!("✓ Hook created: {}", hook_file.display().to_string().bright_yellow());
+        println!("✓ Hook created: {} {}", hook_file.display().to_string().bright_yellow(), format!("(from {})", source).dimmed());
 
         if edit {
             self.open_in_editor(&hook_file)?;
@@ -203,7 +202,23 @@ impl AddCommand {
         Ok(())
     }
 
-    fn open_in_editor(&self, path: &PathBuf) -> Result<()> {
+    /// Render a component template for the current project, with `name` set to the component name
+    fn render(&self, name: &str, content: &str) -> Result<String> {
+        let mut context = ProjectContext::detect_any(Path::new("."));
+        let registry = TemplateRegistry::new();
+        if let Ok(plan) = registry
+            .install_plan(&context.language_id)
+            .or_else(|_| registry.install_plan("core"))
+        {
+            context.vars = plan.resolve_variables(&Default::default())?;
+        }
+
+        TemplateRenderer::new(&context)?
+            .with_var("name", name)
+            .render(name, content)
+    }
+
+    fn open_in_editor(&self, path: &Path) -> Result<()> {
         let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
 
         println!("Opening in {}...", editor);
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::core::settings::LayeredSettings;
+use crate::core::validator::ConfigValidator;
 
 /// Configuration management
 #[derive(Args)]
@@ -50,30 +53,7 @@ impl ConfigCommand {
             anyhow::bail!("No .claude directory found. Run 'claude-forge init' first.");
         }
 
-        let mut errors: Vec<String> = Vec::new();
-        let mut warnings: Vec<String> = Vec::new();
-
-        // Check CLAUDE.md
-        let claude_md = claude_dir.join("CLAUDE.md");
-        if !claude_md.exists() {
-            errors.push("CLAUDE.md not found".to_string());
-        }
-
-        // Check config.json
-        let config_json = claude_dir.join("config.json");
-        if config_json.exists() {
-            match std::fs::read_to_string(&config_json) {
-                Ok(content) => {
-                    if let Err(e) = serde_json::from_str::<serde_json::Value>(&content) {
-                        errors.push("config.json is not valid JSON".to_string());
-                        warnings.push(format!("JSON error: {}", e));
-                    }
-                }
-                Err(_) => errors.push("Cannot read config.json".to_string()),
-            }
-        } else {
-            warnings.push("config.json not found (optional)".to_string());
-        }
+        let report = ConfigValidator::new().validate(Path::new("."))?;
 
         // Check agents directory
         let agents_dir = claude_dir.join("agents");
@@ -99,21 +79,21 @@ impl ConfigCommand {
         // Print results
         println!();
 
-        if !errors.is_empty() {
+        if !report.errors.is_empty() {
             println!("{}", "❌ Errors:".red().bold());
-            for error in &errors {
+            for error in &report.errors {
                 println!("  • {}", error.red());
             }
         }
 
-        if !warnings.is_empty() {
+        if !report.warnings.is_empty() {
             println!("{}", "⚠ Warnings:".yellow().bold());
-            for warning in &warnings {
+            for warning in &report.warnings {
                 println!("  • {}", warning.yellow());
             }
         }
 
-        if errors.is_empty() && warnings.is_empty() {
+        if report.is_clean() {
             println!("{}", "✅ Configuration is valid!".green().bold());
         }
 
@@ -123,6 +103,30 @@ impl ConfigCommand {
     async fn show_config(&self) -> Result<()> {
         println!("{}", "📋 Current Configuration:\n".bright_cyan().bold());
 
+        // Show effective settings, merged from user, project and local files
+        let settings = LayeredSettings::load(Path::new("."))?;
+        if !settings.layers.is_empty() {
+            println!("{}", "Settings files (lowest precedence first):".bright_yellow());
+            for (layer, path) in &settings.layers {
+                println!("  {:<8} {}", layer.to_string().bright_blue(), path.display());
+            }
+
+            println!("\n{}", "Effective settings:".bright_yellow());
+            for origin in settings.origins() {
+                println!(
+                    "  {} = {} {}",
+                    origin.key,
+                    origin.value,
+                    format!("({})", origin.layer).dimmed()
+                );
+            }
+
+            if let Err(e) = settings.effective() {
+                println!("\n{} {:#}", "⚠".yellow(), e);
+            }
+            println!();
+        }
+
         let claude_dir = PathBuf::from(".claude");
         if !claude_dir.exists() {
             println!("No configuration found.");
@@ -136,7 +140,7 @@ impl ConfigCommand {
         Ok(())
     }
 
-    fn print_directory_tree(&self, dir: &PathBuf, depth: usize) -> Result<()> {
+    fn print_directory_tree(&self, dir: &Path, depth: usize) -> Result<()> {
         let indent = "  ".repeat(depth);
 
         for entry in std::fs::read_dir(dir)? {
@@ -184,7 +188,7 @@ impl ConfigCommand {
         Ok(())
     }
 
-    async fn export_config(&self, output: &PathBuf) -> Result<()> {
+    async fn export_config(&self, output: &Path) -> Result<()> {
         println!("{}", format!("📤 Exporting configuration to {}...", output.display()).bright_blue());
 
         // TODO: Implement export functionality
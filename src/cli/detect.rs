@@ -0,0 +1,56 @@
+use anyhow::Result;
+use clap::Args;
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::core::detector::ProjectDetector;
+
+/// Show how the project's language is detected
+#[derive(Args)]
+pub struct DetectCommand {
+    /// Project directory (defaults to current directory)
+    #[arg(short, long, value_name = "PATH")]
+    path: Option<PathBuf>,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    pub(super) json: bool,
+}
+
+impl DetectCommand {
+    pub async fn execute(self) -> Result<()> {
+        let root = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+        let report = ProjectDetector::new().score(&root);
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+
+        println!("{}", "🔍 Detecting project language...\n".bright_blue());
+
+        if report.scores.is_empty() {
+            println!("No supported language found.");
+            return Ok(());
+        }
+
+        for (rank, score) in report.scores.iter().enumerate() {
+            let name = format!("{:<12}", score.language.to_string());
+            let confidence = format!("{:>3.0}%", score.confidence * 100.0);
+            if rank == 0 {
+                println!("{}. {} {}", rank + 1, name.bright_yellow().bold(), confidence.green());
+            } else {
+                println!("{}. {} {}", rank + 1, name, confidence.dimmed());
+            }
+
+            for evidence in &score.evidence {
+                println!("     {}", evidence.dimmed());
+            }
+            for framework in &score.frameworks {
+                println!("     framework: {} {}", framework.name.bright_cyan(), format!("({})", framework.evidence).dimmed());
+            }
+        }
+
+        Ok(())
+    }
+}
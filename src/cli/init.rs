@@ -2,10 +2,15 @@ use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize;
 use dialoguer::{Confirm, Select};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-use crate::core::{detector::ProjectDetector, generator::ConfigGenerator, Language};
+use crate::core::generator::{ChangeAction, ConfigGenerator, PlannedChange};
+use crate::core::validator::ConfigValidator;
+use crate::core::{detector::ProjectDetector, Language};
 use crate::tools::installer::ToolsInstaller;
+use crate::tools::manifest::{project_catalog, ToolsLock};
+use crate::utils::diff::unified_diff;
 use crate::utils::fs::ensure_directory;
 
 /// Initialize Claude Code configuration for a project
@@ -34,6 +39,24 @@ pub struct InitCommand {
     /// Use minimal template (only essential files)
     #[arg(long)]
     minimal: bool,
+
+    /// Show the planned changes and a diff of each file without writing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Overwrite files that were edited since they were generated
+    #[arg(short, long)]
+    force: bool,
+
+    /// Override a template pack variable (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    vars: Vec<(String, String)>,
+}
+
+pub(super) fn parse_var(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", s))
 }
 
 impl InitCommand {
@@ -63,49 +86,137 @@ impl InitCommand {
             } else {
                 detected
             }
+        } else if let Some(project) = detector.detect_projects(&target_path).first() {
+            // No manifest at the root, e.g. a monorepo of packages: default to the first package's language
+            println!(
+                "✓ Detected language: {} {}",
+                project.language.to_string().bright_yellow(),
+                format!("(from {})", project.path.display()).dimmed()
+            );
+            project.language
         } else {
             if self.yes {
-                println!("⚠ Could not detect language, using TypeScript as default");
-                Language::TypeScript
+                let supported: Vec<&str> = Language::all().map(Language::id).collect();
+                anyhow::bail!(
+                    "Could not detect the project language. Pass --lang with one of: {}",
+                    supported.join(", ")
+                );
             } else {
                 self.prompt_for_language()?
             }
         };
 
-        // Step 2: Create .claude directory structure
+        // Step 2: Plan the changes before touching anything
+        let variables: BTreeMap<String, String> = self.vars.iter().cloned().collect();
+        let generator = ConfigGenerator::new(&target_path, language, self.minimal, &variables)?;
+        let changes = generator.plan_changes(&target_path)?;
+
+        for pack in &generator.plan().packs {
+            let info = &pack.manifest.pack;
+            println!("  📦 {} {} {}", info.name.bright_cyan(), info.version.dimmed(), info.description.as_deref().unwrap_or("").dimmed());
+        }
+        if !generator.packages().is_empty() {
+            println!("  {}", "Packages:".bright_cyan());
+            for package in generator.packages() {
+                println!("    {} {}", package.path.display().to_string().bright_yellow(), package.language.to_string().dimmed());
+            }
+        }
+        self.print_plan(&changes);
+
+        if self.dry_run {
+            self.print_diffs(&changes);
+            println!("\n{}", "Dry run mode - no changes made".yellow());
+            return Ok(());
+        }
+
+        // Step 3: Create .claude directory structure
         let claude_dir = target_path.join(".claude");
         self.create_directory_structure(&claude_dir)?;
 
-        // Step 3: Generate configuration files
-        let generator = ConfigGenerator::new(language, self.minimal);
-        generator.generate(&target_path).await?;
+        // Step 4: Write configuration files
+        let applied = generator.apply(&target_path, &changes, self.force)?;
 
         println!("\n{}", "✓ Configuration files generated".green());
+        for file in applied.generated.iter().filter(|file| applied.written.contains(&file.path)) {
+            println!("  {} {}", file.path.display().to_string().bright_yellow(), format!("({}, {})", file.pack, file.layer).dimmed());
+        }
+        if !applied.skipped.is_empty() {
+            println!(
+                "{} Kept {} file(s) with local edits. Use --force to overwrite them, or 'claude-forge sync' to merge.",
+                "⚠".yellow(),
+                applied.skipped.len()
+            );
+        }
+        if let Some(backup) = &applied.backup {
+            println!("  Backup: {} (undo with {})", backup.dimmed(), "claude-forge restore".bright_yellow());
+        }
 
-        // Step 4: Install modern CLI tools (optional)
-        if !self.no_tools {
-            if self.yes || self.prompt_install_tools()? {
-                println!("\n{}", "📦 Checking modern CLI tools...".bright_blue());
-                let installer = ToolsInstaller::new();
-                installer.check_and_install().await?;
-            }
+        // The same checks as `config validate`, on what was just written
+        for error in ConfigValidator::new().validate(&target_path)?.errors {
+            println!("{} {}", "⚠".yellow(), error);
         }
 
-        // Step 5: Configure MCP (optional)
-        if !self.no_mcp {
-            if self.yes || self.prompt_configure_mcp()? {
-                println!("\n{}", "🔌 Configuring MCP servers...".bright_blue());
-                self.configure_mcp(&target_path, language).await?;
-            }
+        for tool in generator.plan().missing_tools() {
+            println!("{} {} is required by the installed templates but was not found on PATH", "⚠".yellow(), tool.bright_yellow());
         }
 
-        // Step 6: Print success message with next steps
+        // Step 5: Install modern CLI tools (optional)
+        if !self.no_tools && (self.yes || self.prompt_install_tools(&target_path)?) {
+            println!("\n{}", "📦 Checking modern CLI tools...".bright_blue());
+            let installer = ToolsInstaller::new(project_catalog(&target_path)?)?
+                .with_lock(ToolsLock::load(&target_path)?);
+            installer.check_and_install().await?;
+        }
+
+        // Step 6: Configure MCP (optional)
+        if !self.no_mcp && (self.yes || self.prompt_configure_mcp()?) {
+            println!("\n{}", "🔌 Configuring MCP servers...".bright_blue());
+            self.configure_mcp(&target_path, language).await?;
+        }
+
+        // Step 7: Print success message with next steps
         self.print_success_message(&target_path, language);
 
         Ok(())
     }
 
-    fn create_directory_structure(&self, claude_dir: &PathBuf) -> Result<()> {
+    fn print_plan(&self, changes: &[PlannedChange]) {
+        println!("\n{}", "📋 Plan:".bright_blue());
+
+        let mut unchanged = 0;
+        for change in changes {
+            let path = change.path.display().to_string();
+            match change.action {
+                ChangeAction::Create => println!("  {} {}", "+".green(), path),
+                ChangeAction::Overwrite => println!("  {} {}", "~".cyan(), path),
+                ChangeAction::Modified if self.force => {
+                    println!("  {} {} {}", "!".red(), path, "(edited locally, will be overwritten)".dimmed())
+                }
+                ChangeAction::Modified => {
+                    println!("  {} {} {}", "-".yellow(), path, "(edited locally, skipped)".dimmed())
+                }
+                ChangeAction::Unchanged => unchanged += 1,
+            }
+        }
+
+        if unchanged > 0 {
+            println!("  {}", format!("{} file(s) unchanged", unchanged).dimmed());
+        }
+    }
+
+    fn print_diffs(&self, changes: &[PlannedChange]) {
+        for change in changes {
+            if change.action == ChangeAction::Unchanged {
+                continue;
+            }
+
+            let path = change.path.to_string_lossy();
+            let current = change.current.as_deref().unwrap_or("");
+            print!("\n{}", unified_diff(current, &change.content, &format!("a/{}", path), &format!("b/{}", path)));
+        }
+    }
+
+    fn create_directory_structure(&self, claude_dir: &Path) -> Result<()> {
         println!("📁 Creating directory structure...");
 
         ensure_directory(claude_dir)?;
@@ -118,29 +229,22 @@ impl InitCommand {
     }
 
     fn prompt_for_language(&self) -> Result<Language> {
-        let languages = vec![
-            "TypeScript",
-            "JavaScript",
-            "Python",
-            "Go",
-            "Rust",
-            "Java",
-            "Elixir",
-            "Erlang",
-        ];
+        let languages: Vec<Language> = Language::all().collect();
+        let names: Vec<&str> = languages.iter().map(|language| language.to_string()).collect();
 
         let selection = Select::new()
             .with_prompt("Select your project language")
-            .items(&languages)
-            .default(0)
+            .items(&names)
+            .default(languages.iter().position(|language| *language == Language::TypeScript).unwrap_or(0))
             .interact()?;
 
-        Language::from_str(languages[selection])
+        Ok(languages[selection])
     }
 
-    fn prompt_install_tools(&self) -> Result<bool> {
+    fn prompt_install_tools(&self, target_path: &Path) -> Result<bool> {
+        let binaries: Vec<String> = project_catalog(target_path)?.tools.into_iter().map(|tool| tool.binary).collect();
         Confirm::new()
-            .with_prompt("Install modern CLI tools (rg, fd, bat, eza, dust)?")
+            .with_prompt(format!("Install modern CLI tools ({})?", binaries.join(", ")))
             .default(true)
             .interact()
             .context("Failed to prompt for tools installation")
@@ -154,13 +258,13 @@ impl InitCommand {
             .context("Failed to prompt for MCP configuration")
     }
 
-    async fn configure_mcp(&self, _target_path: &PathBuf, _language: Language) -> Result<()> {
+    async fn configure_mcp(&self, _target_path: &Path, _language: Language) -> Result<()> {
         // TODO: Implement MCP configuration
         println!("✓ MCP configuration added");
         Ok(())
     }
 
-    fn print_success_message(&self, target_path: &PathBuf, language: Language) {
+    fn print_success_message(&self, target_path: &Path, language: Language) {
         println!("\n{}", "🎉 Success!".bright_green().bold());
         println!("\nClaude Code configuration has been initialized for your {} project!",
                  language.to_string().bright_yellow());
@@ -5,6 +5,11 @@ mod mcp;
 mod config;
 mod upgrade;
 mod update;
+mod templates;
+mod sync;
+mod restore;
+mod permissions;
+mod detect;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -17,6 +22,11 @@ pub use mcp::McpCommand;
 pub use config::ConfigCommand;
 pub use upgrade::UpgradeCommand;
 pub use update::UpdateCommand;
+pub use templates::TemplatesCommand;
+pub use sync::SyncCommand;
+pub use restore::RestoreCommand;
+pub use permissions::PermissionsCommand;
+pub use detect::DetectCommand;
 
 /// Claude Forge - A CLI tool for managing Claude Code configurations
 #[derive(Parser)]
@@ -64,6 +74,22 @@ enum Commands {
     /// Update templates to latest version
     #[command(alias = "upd")]
     Update(UpdateCommand),
+
+    /// List, show, diff and install templates
+    #[command(alias = "tpl")]
+    Templates(TemplatesCommand),
+
+    /// Re-apply updated templates, merging them with local edits
+    Sync(SyncCommand),
+
+    /// Roll back the files changed by init from a backup
+    Restore(RestoreCommand),
+
+    /// Manage permission allow/ask/deny rules
+    Permissions(PermissionsCommand),
+
+    /// Show the detected language, with the evidence behind each score
+    Detect(DetectCommand),
 }
 
 impl Cli {
@@ -73,8 +99,10 @@ impl Cli {
             std::env::set_var("RUST_LOG", "debug");
         }
 
-        // Print banner
-        self.print_banner();
+        // Print banner, unless the output is meant for other programs
+        if !matches!(&self.command, Commands::Detect(cmd) if cmd.json) {
+            self.print_banner();
+        }
 
         // Execute the appropriate command
         match self.command {
@@ -85,6 +113,11 @@ impl Cli {
             Commands::Config(cmd) => cmd.execute().await,
             Commands::Upgrade(cmd) => cmd.execute().await,
             Commands::Update(cmd) => cmd.execute().await,
+            Commands::Templates(cmd) => cmd.execute().await,
+            Commands::Sync(cmd) => cmd.execute().await,
+            Commands::Restore(cmd) => cmd.execute().await,
+            Commands::Permissions(cmd) => cmd.execute().await,
+            Commands::Detect(cmd) => cmd.execute().await,
         }
     }
 
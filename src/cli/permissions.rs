@@ -0,0 +1,182 @@
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::core::permissions::{audit, PermissionKind, SuggestedPermissions};
+use crate::core::settings::{LayeredSettings, Settings, SETTINGS_FILE};
+use crate::templates::context::ProjectContext;
+
+/// Manage permission rules in .claude/settings.json
+#[derive(Args)]
+pub struct PermissionsCommand {
+    #[command(subcommand)]
+    action: PermissionsAction,
+
+    /// Project directory (defaults to current directory)
+    #[arg(short, long, value_name = "PATH", global = true)]
+    path: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum PermissionsAction {
+    /// List the project's rules and the rules suggested for its tooling
+    List,
+
+    /// Add rules, e.g. 'Bash(cargo test:*)' or 'Read(./secrets/**)'
+    Add {
+        /// Rules to add
+        #[arg(required_unless_present = "suggested")]
+        rules: Vec<String>,
+
+        /// Add to the deny list instead of allow
+        #[arg(long, conflicts_with = "ask")]
+        deny: bool,
+
+        /// Add to the ask list instead of allow
+        #[arg(long)]
+        ask: bool,
+
+        /// Also add every rule suggested for the project's tooling
+        #[arg(long)]
+        suggested: bool,
+    },
+
+    /// Remove rules from every list
+    Remove {
+        /// Rules to remove
+        #[arg(required = true)]
+        rules: Vec<String>,
+    },
+
+    /// Check the effective rules (user, project and local settings) for problems
+    Audit,
+}
+
+impl PermissionsCommand {
+    pub async fn execute(self) -> Result<()> {
+        let root = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+
+        match &self.action {
+            PermissionsAction::List => self.list(&root),
+            PermissionsAction::Add { rules, deny, ask, suggested } => {
+                let kind = if *deny {
+                    PermissionKind::Deny
+                } else if *ask {
+                    PermissionKind::Ask
+                } else {
+                    PermissionKind::Allow
+                };
+                self.add(&root, kind, rules, *suggested)
+            }
+            PermissionsAction::Remove { rules } => self.remove(&root, rules),
+            PermissionsAction::Audit => self.audit(&root),
+        }
+    }
+
+    fn list(&self, root: &Path) -> Result<()> {
+        let settings = Settings::load(&root.join(SETTINGS_FILE))?;
+        let permissions = settings.permissions.unwrap_or_default();
+
+        for kind in [PermissionKind::Allow, PermissionKind::Ask, PermissionKind::Deny] {
+            let rules = permissions.rules(kind);
+            if rules.is_empty() {
+                continue;
+            }
+
+            println!("{}", format!("{}:", kind).bright_yellow());
+            for rule in rules {
+                println!("  {}", rule);
+            }
+        }
+
+        let missing: Vec<_> = suggested(root)
+            .rules()
+            .filter(|(kind, rule)| !permissions.rules(*kind).iter().any(|existing| existing == rule))
+            .map(|(kind, rule)| (kind, rule.to_string()))
+            .collect();
+        if !missing.is_empty() {
+            println!("\n{}", "Suggested for this project:".bright_yellow());
+            for (kind, rule) in &missing {
+                println!("  {} {}", rule, format!("({})", kind).dimmed());
+            }
+            println!("\nAdd them with {}", "claude-forge permissions add --suggested".bright_yellow());
+        }
+
+        Ok(())
+    }
+
+    fn add(&self, root: &Path, kind: PermissionKind, rules: &[String], with_suggested: bool) -> Result<()> {
+        let path = root.join(SETTINGS_FILE);
+        let mut settings = Settings::load(&path)?;
+
+        let mut added = Vec::new();
+        for rule in rules {
+            if settings.add_permission(kind, rule) {
+                added.push((kind, rule.clone()));
+            }
+        }
+        if with_suggested {
+            for (kind, rule) in suggested(root).rules() {
+                if settings.add_permission(kind, rule) {
+                    added.push((kind, rule.to_string()));
+                }
+            }
+        }
+
+        if added.is_empty() {
+            println!("All rules are already present.");
+            return Ok(());
+        }
+
+        settings.save(&path)?;
+        for (kind, rule) in &added {
+            println!("✓ {} {}", rule, format!("({})", kind).dimmed());
+        }
+        println!("\n{}", format!("✅ Added {} rule(s) to {}", added.len(), SETTINGS_FILE).green().bold());
+
+        Ok(())
+    }
+
+    fn remove(&self, root: &Path, rules: &[String]) -> Result<()> {
+        let path = root.join(SETTINGS_FILE);
+        let mut settings = Settings::load(&path)?;
+
+        let (removed, missing): (Vec<&String>, Vec<&String>) =
+            rules.iter().partition(|rule| settings.remove_permission(rule));
+        if !missing.is_empty() {
+            let missing: Vec<&str> = missing.iter().map(|rule| rule.as_str()).collect();
+            bail!("Rule(s) not found in {}: {}", SETTINGS_FILE, missing.join(", "));
+        }
+
+        settings.save(&path)?;
+        for rule in &removed {
+            println!("✓ Removed {}", rule);
+        }
+
+        Ok(())
+    }
+
+    fn audit(&self, root: &Path) -> Result<()> {
+        println!("{}", "🔍 Auditing permission rules...\n".bright_blue());
+
+        let permissions = LayeredSettings::load(root)?.effective()?.permissions.unwrap_or_default();
+        let findings = audit(&permissions);
+        if findings.is_empty() {
+            println!("{}", "✅ No problems found".green().bold());
+            return Ok(());
+        }
+
+        println!("{}", "⚠ Warnings:".yellow().bold());
+        for finding in &findings {
+            println!("  • {}", finding.yellow());
+        }
+        println!("\nFix them with {}", "claude-forge permissions add|remove".bright_yellow());
+
+        Ok(())
+    }
+}
+
+fn suggested(root: &Path) -> SuggestedPermissions {
+    SuggestedPermissions::from_commands(&ProjectContext::detect_any(root).commands)
+}
@@ -0,0 +1,75 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use colored::Colorize;
+use dialoguer::Confirm;
+use std::path::PathBuf;
+
+use crate::core::backup::Backup;
+
+/// Roll back the files changed by `init`
+#[derive(Args)]
+pub struct RestoreCommand {
+    /// Backup to restore (defaults to the most recent)
+    backup: Option<String>,
+
+    /// Project directory (defaults to current directory)
+    #[arg(short, long, value_name = "PATH")]
+    path: Option<PathBuf>,
+
+    /// List available backups
+    #[arg(short, long)]
+    list: bool,
+
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    yes: bool,
+}
+
+impl RestoreCommand {
+    pub async fn execute(self) -> Result<()> {
+        let root = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+        let backups = Backup::list(&root)?;
+
+        if self.list {
+            if backups.is_empty() {
+                println!("No backups found.");
+            }
+            for backup in &backups {
+                println!(
+                    "  {} {}",
+                    backup.id.bright_yellow(),
+                    format!("({} replaced, {} created)", backup.saved, backup.created).dimmed()
+                );
+            }
+            return Ok(());
+        }
+
+        let id = match &self.backup {
+            Some(id) => id.clone(),
+            None => match backups.first() {
+                Some(latest) => latest.id.clone(),
+                None => bail!("No backups found to restore."),
+            },
+        };
+
+        if !self.yes {
+            let confirm = Confirm::new()
+                .with_prompt(format!("Restore backup {}? Files changed since will be lost.", id))
+                .default(false)
+                .interact()?;
+
+            if !confirm {
+                println!("Restore cancelled.");
+                return Ok(());
+            }
+        }
+
+        println!("{}", format!("⏪ Restoring backup {}...\n", id).bright_blue());
+        for path in Backup::restore(&root, &id)? {
+            println!("✓ {}", path);
+        }
+        println!("\n{}", "✅ Restore complete!".green().bold());
+
+        Ok(())
+    }
+}
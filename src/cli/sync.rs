@@ -0,0 +1,223 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::generator::{ConfigGenerator, GeneratedFile};
+use crate::core::lock::{content_hash, read_base, write_base, FileStatus, ForgeLock};
+use crate::core::merge::merge3;
+use crate::core::{detector::ProjectDetector, Language};
+use crate::templates::registry::TemplateRegistry;
+use crate::utils::fs::{read_file, set_executable, write_file};
+
+/// Re-apply updated templates to the project, keeping local edits
+#[derive(Args)]
+pub struct SyncCommand {
+    /// Project directory (defaults to current directory)
+    #[arg(short, long, value_name = "PATH")]
+    path: Option<PathBuf>,
+
+    /// Specify language explicitly instead of detecting it
+    #[arg(short, long, value_name = "LANGUAGE")]
+    lang: Option<String>,
+
+    /// Report what would change without writing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// What syncing did to a single file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Unchanged,
+    Updated,
+    Merged,
+    Conflicted,
+    Added,
+    DeletedLocally,
+    Untracked,
+}
+
+impl Outcome {
+    fn label(self) -> colored::ColoredString {
+        match self {
+            Outcome::Unchanged => "unchanged".dimmed(),
+            Outcome::Updated => "updated".green(),
+            Outcome::Merged => "merged".cyan(),
+            Outcome::Conflicted => "conflict".red().bold(),
+            Outcome::Added => "added".green(),
+            Outcome::DeletedLocally => "deleted locally, skipped".yellow(),
+            Outcome::Untracked => "exists but not generated, skipped".yellow(),
+        }
+    }
+}
+
+impl SyncCommand {
+    pub async fn execute(self) -> Result<()> {
+        let root = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+        let lock = ForgeLock::load(&root)?;
+        if lock.packs.is_empty() {
+            bail!("No .claude/forge.lock found. Run 'claude-forge init' first.");
+        }
+
+        println!("{}", "🔄 Syncing templates...\n".bright_blue());
+
+        let language = self.language(&root)?;
+        let generators = self.generators(&root, language, &lock)?;
+        let installed: Vec<&str> = lock.packs.iter().map(|pack| pack.name.as_str()).collect();
+
+        let mut seen: Vec<PathBuf> = Vec::new();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+
+        for generator in &generators {
+            let mut synced = Vec::new();
+
+            for file in &generator.plan().files {
+                if seen.contains(&file.dest) || !installed.contains(&file.pack.as_str()) {
+                    continue;
+                }
+
+                // A file recorded in the lockfile comes from the pack recorded for it
+                let locked = lock.file(&file.dest);
+                if locked.is_some_and(|locked| locked.pack != file.pack) {
+                    continue;
+                }
+                if locked.is_none() && lock.minimal && !file.essential {
+                    continue;
+                }
+                seen.push(file.dest.clone());
+
+                let template = generator.render_file(file)?;
+                let path = root.join(&file.dest);
+
+                let (outcome, content) = match lock.status(&root, &file.dest) {
+                    FileStatus::Pristine => {
+                        let current = read_file(&path)?;
+                        if current == template.content {
+                            (Outcome::Unchanged, None)
+                        } else {
+                            (Outcome::Updated, Some(template.content.clone()))
+                        }
+                    }
+                    FileStatus::Modified => {
+                        let current = read_file(&path)?;
+                        let base = read_base(&root, &file.dest).unwrap_or_default();
+                        let merged = merge3(&base, &current, &template.content);
+
+                        if merged.content == current {
+                            (Outcome::Unchanged, None)
+                        } else if merged.conflicts > 0 {
+                            (Outcome::Conflicted, Some(merged.content))
+                        } else {
+                            (Outcome::Merged, Some(merged.content))
+                        }
+                    }
+                    FileStatus::Missing => (Outcome::DeletedLocally, None),
+                    FileStatus::Untracked if path.exists() => (Outcome::Untracked, None),
+                    FileStatus::Untracked => (Outcome::Added, Some(template.content.clone())),
+                };
+
+                *counts.entry(outcome_name(outcome)).or_default() += 1;
+                if outcome != Outcome::Unchanged {
+                    println!("  {} {}", file.dest.display(), format!("({})", outcome.label()).dimmed());
+                }
+
+                if matches!(outcome, Outcome::DeletedLocally | Outcome::Untracked) {
+                    continue;
+                }
+
+                if !self.dry_run {
+                    if let Some(content) = content {
+                        write_file(&path, &content)?;
+                        if file.executable {
+                            set_executable(&path)?;
+                        }
+                    }
+                    write_base(&root, &file.dest, &template.content)?;
+                }
+
+                synced.push(GeneratedFile {
+                    path: file.dest.clone(),
+                    pack: file.pack.clone(),
+                    layer: template.layer,
+                    template: file.source.clone(),
+                    hash: content_hash(&template.content),
+                });
+            }
+
+            if !self.dry_run {
+                generator.update_lock(&root, &synced)?;
+            }
+        }
+
+        let summary: Vec<String> = counts
+            .iter()
+            .map(|(name, count)| format!("{} {}", count, name))
+            .collect();
+        println!("\n{}", summary.join(", "));
+
+        if counts.contains_key("conflicted") {
+            println!(
+                "{}",
+                "⚠ Resolve the <<<<<<< / >>>>>>> conflict markers in the files above.".yellow()
+            );
+        }
+        if self.dry_run {
+            println!("{}", "Dry run mode - no changes made".yellow());
+        } else {
+            println!("{}", "✅ Sync complete!".green().bold());
+        }
+
+        Ok(())
+    }
+
+    fn language(&self, root: &Path) -> Result<Language> {
+        if let Some(lang) = &self.lang {
+            return Language::from_str(lang);
+        }
+
+        ProjectDetector::new()
+            .detect(root)
+            .map_err(|_| anyhow::anyhow!("Could not detect the project language. Use --lang to specify it."))
+    }
+
+    /// One generator per installed pack that no other installed pack extends, rendering
+    /// with the variables recorded in the lockfile
+    fn generators(&self, root: &Path, language: Language, lock: &ForgeLock) -> Result<Vec<ConfigGenerator>> {
+        let registry = TemplateRegistry::for_project(root);
+        let mut generators: Vec<ConfigGenerator> = Vec::new();
+
+        for pack in lock.packs.iter().rev() {
+            let covered = generators
+                .iter()
+                .any(|generator| generator.plan().packs.iter().any(|p| p.name() == pack.name));
+            if covered {
+                continue;
+            }
+
+            let plan = registry.install_plan(&pack.name)?;
+            let variables: BTreeMap<String, String> = lock
+                .variables
+                .iter()
+                .filter(|(key, _)| plan.variables.contains_key(*key))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+
+            generators.push(ConfigGenerator::for_pack(root, language, &pack.name, &variables)?);
+        }
+
+        Ok(generators)
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Unchanged => "unchanged",
+        Outcome::Updated => "updated",
+        Outcome::Merged => "merged",
+        Outcome::Conflicted => "conflicted",
+        Outcome::Added => "added",
+        Outcome::DeletedLocally | Outcome::Untracked => "skipped",
+    }
+}
@@ -0,0 +1,375 @@
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::init::parse_var;
+use crate::core::lock::{FileStatus, ForgeLock};
+use crate::core::{detector::ProjectDetector, generator::ConfigGenerator, Language};
+use crate::templates::pack::PackFile;
+use crate::templates::registry::{TemplateKind, TemplateRegistry};
+use crate::templates::source::{SourceLocation, TemplateSource, TemplateSources};
+use crate::utils::diff::unified_diff;
+use crate::utils::fs::read_file;
+
+/// Inspect and install templates
+#[derive(Args)]
+pub struct TemplatesCommand {
+    #[command(subcommand)]
+    action: TemplatesAction,
+
+    /// Project directory (defaults to current directory)
+    #[arg(short, long, value_name = "PATH", global = true)]
+    path: Option<PathBuf>,
+
+    /// Specify language explicitly instead of detecting it
+    #[arg(short, long, value_name = "LANGUAGE", global = true)]
+    lang: Option<String>,
+
+    /// Override a template pack variable (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var, global = true)]
+    vars: Vec<(String, String)>,
+}
+
+#[derive(Subcommand)]
+enum TemplatesAction {
+    /// List packs, agents, commands and hooks from every template layer
+    List,
+
+    /// Print a template rendered for the current project
+    Show {
+        /// Installed path (e.g. .claude/CLAUDE.md), template path or component name
+        target: String,
+
+        /// Pack to render from (defaults to the project's language pack)
+        #[arg(long)]
+        pack: Option<String>,
+    },
+
+    /// Diff installed files against the templates they came from
+    Diff {
+        /// Installed path to diff (defaults to every file from the language pack)
+        file: Option<PathBuf>,
+
+        /// Pack to compare against (defaults to the project's language pack)
+        #[arg(long)]
+        pack: Option<String>,
+    },
+
+    /// Install a single pack into an existing .claude directory
+    Install {
+        /// Name of the pack
+        pack: String,
+
+        /// Also install the packs it extends
+        #[arg(long)]
+        with_deps: bool,
+
+        /// Overwrite files that already exist
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Manage additional template sources (git repositories or local directories)
+    Source {
+        #[command(subcommand)]
+        action: SourceAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum SourceAction {
+    /// List registered sources in precedence order
+    List,
+
+    /// Register a source; it takes precedence over older sources and embedded templates
+    Add {
+        /// Name of the source
+        name: String,
+
+        /// Git URL or local directory
+        location: String,
+
+        /// Branch, tag or commit to pin a git source to
+        #[arg(long)]
+        rev: Option<String>,
+
+        /// Sub-directory holding the template tree
+        #[arg(long)]
+        subdir: Option<String>,
+    },
+
+    /// Fetch git sources again and check out their pinned revision
+    Update {
+        /// Source to update (defaults to all)
+        name: Option<String>,
+
+        /// Pin the source to a new revision
+        #[arg(long, requires = "name")]
+        rev: Option<String>,
+    },
+
+    /// Unregister a source and delete its cache
+    Remove {
+        /// Name of the source
+        name: String,
+    },
+}
+
+impl TemplatesCommand {
+    pub async fn execute(self) -> Result<()> {
+        match self.action {
+            TemplatesAction::List => self.list(),
+            TemplatesAction::Show { ref target, ref pack } => self.show(target, pack.as_deref()),
+            TemplatesAction::Diff { ref file, ref pack } => self.diff(file.as_deref(), pack.as_deref()),
+            TemplatesAction::Install { ref pack, with_deps, force } => self.install(pack, with_deps, force),
+            TemplatesAction::Source { ref action } => self.source(action),
+        }
+    }
+
+    fn project_root(&self) -> PathBuf {
+        self.path.clone().unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    fn language(&self) -> Result<Language> {
+        if let Some(lang) = &self.lang {
+            return Language::from_str(lang);
+        }
+
+        ProjectDetector::new()
+            .detect(&self.project_root())
+            .map_err(|_| anyhow::anyhow!("Could not detect the project language. Use --lang to specify it."))
+    }
+
+    fn generator(&self, pack: Option<&str>) -> Result<ConfigGenerator> {
+        let root = self.project_root();
+        let language = self.language()?;
+        let vars: BTreeMap<String, String> = self.vars.iter().cloned().collect();
+
+        match pack {
+            Some(pack) => ConfigGenerator::for_pack(&root, language, pack, &vars),
+            None => ConfigGenerator::new(&root, language, false, &vars),
+        }
+    }
+
+    fn list(&self) -> Result<()> {
+        let registry = TemplateRegistry::for_project(&self.project_root());
+
+        println!("{}", "📦 Packs:".bright_blue().bold());
+        for pack in registry.list_packs()? {
+            let info = &pack.manifest.pack;
+            let extends = if info.extends.is_empty() {
+                String::new()
+            } else {
+                format!(" extends {}", info.extends.join(", "))
+            };
+            println!("  {} {}{} [{}]", info.name.bright_green(), info.version.dimmed(), extends.dimmed(), pack.layer);
+            if let Some(description) = &info.description {
+                println!("    {}", description);
+            }
+        }
+
+        for (title, kind) in [
+            ("🤖 Agents:", TemplateKind::Agent),
+            ("⚡ Commands:", TemplateKind::Command),
+            ("🪝 Hooks:", TemplateKind::Hook),
+        ] {
+            println!("\n{}", title.bright_blue().bold());
+            for (root, name, layer) in registry.list_components(kind) {
+                println!("  {} {} [{}]", name.bright_green(), format!("({})", root).dimmed(), layer);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn show(&self, target: &str, pack: Option<&str>) -> Result<()> {
+        let generator = self.generator(pack)?;
+
+        if let Some(file) = find_plan_file(generator.plan().files.as_slice(), target) {
+            let template = generator.render_file(file)?;
+            eprintln!("{}", format!("# {} from {} ({})", file.dest.display(), file.source, template.layer).dimmed());
+            print!("{}", template.content);
+            return Ok(());
+        }
+
+        let registry = TemplateRegistry::for_project(&self.project_root());
+        let template = match registry.read_template(target) {
+            Ok(template) => Some(template),
+            Err(_) => [TemplateKind::Agent, TemplateKind::Command, TemplateKind::Hook]
+                .into_iter()
+                .find_map(|kind| registry.get_template(kind, target)),
+        };
+
+        match template {
+            Some(template) => {
+                eprintln!("{}", format!("# {} ({})", target, template.layer).dimmed());
+                print!("{}", generator.render(target, &template.content)?);
+                Ok(())
+            }
+            None => bail!("No template found for '{}'", target),
+        }
+    }
+
+    fn diff(&self, file: Option<&Path>, pack: Option<&str>) -> Result<()> {
+        let root = self.project_root();
+        let generator = self.generator(pack)?;
+        let files = &generator.plan().files;
+
+        let selected: Vec<&PackFile> = match file {
+            Some(file) => {
+                let target = file.to_string_lossy();
+                match find_plan_file(files, &target) {
+                    Some(found) => vec![found],
+                    None => bail!("{} is not installed by any template in the plan", file.display()),
+                }
+            }
+            None => files.iter().filter(|file| root.join(&file.dest).exists()).collect(),
+        };
+
+        let mut changed = 0;
+        for file in selected {
+            let installed_path = root.join(&file.dest);
+            if !installed_path.exists() {
+                println!("{} {}", "not installed:".yellow(), file.dest.display());
+                continue;
+            }
+
+            let installed = read_file(&installed_path)?;
+            let template = generator.render_file(file)?;
+            if installed == template.content {
+                continue;
+            }
+
+            changed += 1;
+            print!(
+                "{}",
+                unified_diff(&template.content, &installed, &format!("template/{}", file.source), &file.dest.to_string_lossy())
+            );
+        }
+
+        if changed == 0 {
+            println!("{}", "✓ Installed files match their templates".green());
+        }
+
+        Ok(())
+    }
+
+    fn install(&self, pack: &str, with_deps: bool, force: bool) -> Result<()> {
+        let root = self.project_root();
+        let claude_dir = root.join(".claude");
+        if !claude_dir.exists() {
+            bail!("No .claude directory found. Run 'claude-forge init' first.");
+        }
+
+        let generator = self.generator(Some(pack))?;
+        let packs: Vec<&str> = if with_deps {
+            generator.plan().packs.iter().map(|pack| pack.name()).collect()
+        } else {
+            vec![pack]
+        };
+
+        println!("{}", format!("📦 Installing {}...\n", packs.join(", ")).bright_blue());
+
+        // Files left as generated can be replaced safely; anything else needs --force
+        let lock = ForgeLock::load(&root)?;
+        let mut installed = Vec::new();
+        let mut skipped = 0;
+        for file in generator.plan().files.iter().filter(|file| packs.contains(&file.pack.as_str())) {
+            let status = lock.status(&root, &file.dest);
+            let replaceable = matches!(status, FileStatus::Pristine | FileStatus::Missing);
+            if !force && !replaceable && root.join(&file.dest).exists() {
+                let reason = if status == FileStatus::Modified { "modified" } else { "exists" };
+                println!("{} {} ({})", "-".dimmed(), file.dest.display().to_string().dimmed(), reason);
+                skipped += 1;
+                continue;
+            }
+
+            let generated = generator.install_file(&root, file)?;
+            println!("✓ {} ({}, {})", generated.path.display(), generated.pack, generated.layer);
+            installed.push(generated);
+        }
+
+        generator.merge_hooks_into_settings(&root, &packs)?;
+        generator.update_lock(&root, &installed)?;
+
+        if skipped > 0 {
+            println!("\n{}", format!("⚠ Skipped {} existing file(s). Use --force to overwrite.", skipped).yellow());
+        }
+        println!("\n{}", "✅ Pack installed!".green().bold());
+
+        Ok(())
+    }
+
+    fn source(&self, action: &SourceAction) -> Result<()> {
+        let mut sources = TemplateSources::load()?;
+
+        match action {
+            SourceAction::List => {
+                if sources.sources().is_empty() {
+                    println!("No template sources registered. Add one with 'claude-forge templates source add'.");
+                }
+
+                for source in sources.sources() {
+                    let location = match &source.location {
+                        SourceLocation::Git(url) => url.clone(),
+                        SourceLocation::Path(path) => path.display().to_string(),
+                    };
+                    let pin = match (&source.rev, &source.commit) {
+                        (Some(rev), Some(commit)) => format!(" @ {} ({})", rev, short_commit(commit)),
+                        (None, Some(commit)) => format!(" @ {}", short_commit(commit)),
+                        (Some(rev), None) => format!(" @ {}", rev),
+                        (None, None) => String::new(),
+                    };
+                    println!("  {} {}{}", source.name.bright_green(), location, pin.dimmed());
+                }
+            }
+            SourceAction::Add { name, location, rev, subdir } => {
+                let source = sources
+                    .add(TemplateSource {
+                        name: name.clone(),
+                        location: SourceLocation::parse(location, rev.as_deref())?,
+                        rev: rev.clone(),
+                        commit: None,
+                        subdir: subdir.clone(),
+                    })?
+                    .clone();
+                println!("✓ Added template source: {} ({})", source.name.bright_green(), sources.root(&source).display());
+            }
+            SourceAction::Update { name, rev } => {
+                let names: Vec<String> = match name {
+                    Some(name) => vec![name.clone()],
+                    None => sources.sources().iter().map(|source| source.name.clone()).collect(),
+                };
+
+                for name in names {
+                    let source = sources.update(&name, rev.as_deref())?;
+                    match &source.commit {
+                        Some(commit) => println!("✓ {} at {}", source.name.bright_green(), short_commit(commit)),
+                        None => println!("✓ {} (local directory)", source.name.bright_green()),
+                    }
+                }
+            }
+            SourceAction::Remove { name } => {
+                sources.remove(name)?;
+                println!("✓ Removed template source: {}", name.bright_green());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+/// Find a plan file by its installed path or its path in the template tree
+fn find_plan_file<'a>(files: &'a [PackFile], target: &str) -> Option<&'a PackFile> {
+    let target = target.trim_start_matches("./");
+
+    files
+        .iter()
+        .find(|file| file.dest == Path::new(target) || file.source == target)
+}
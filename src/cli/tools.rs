@@ -1,10 +1,17 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
-use crate::tools::{checker::ToolsChecker, installer::ToolsInstaller};
+use crate::templates::source::forge_config_dir;
+use std::path::Path;
 
-/// Manage modern CLI tools (rg, fd, bat, eza, dust)
+use crate::tools::catalog::{ToolCatalog, CATALOG_FILE};
+use crate::tools::manifest::{project_catalog, ToolsLock, ToolsManifest, LOCK_FILE, MANIFEST_FILE};
+use crate::tools::package_manager::PackageManagers;
+use crate::tools::checker::ToolsChecker;
+use crate::tools::installer::{ToolsInstaller, DEFAULT_JOBS};
+
+/// Manage modern CLI tools from the tool catalog (rg, fd, bat, eza, dust by default)
 #[derive(Args)]
 pub struct ToolsCommand {
     #[command(subcommand)]
@@ -22,6 +29,14 @@ enum ToolsAction {
         /// Show what would be installed without installing
         #[arg(long)]
         dry_run: bool,
+
+        /// How many tools to install at once
+        #[arg(short, long, default_value_t = DEFAULT_JOBS)]
+        jobs: usize,
+
+        /// Uninstall everything this run installed if any tool fails
+        #[arg(long)]
+        rollback: bool,
     },
 
     /// Check if tools are installed
@@ -37,103 +52,213 @@ enum ToolsAction {
         #[arg(long, value_delimiter = ',')]
         skip: Vec<String>,
     },
+
+    /// Record the installed versions of the tools in .claude/tools.toml in .claude/tools.lock
+    Lock,
+
+    /// Remove tools the way claude-forge installed them
+    Uninstall {
+        /// Tools to remove, by name or binary (default: every tool claude-forge installed)
+        tools: Vec<String>,
+    },
 }
 
 impl ToolsCommand {
     pub async fn execute(self) -> Result<()> {
+        // The project's .claude/tools.toml, if any, narrows the catalog to the tools it lists
+        let project_root = Path::new(".");
+        let catalog = project_catalog(project_root)?;
+
         match self.action {
-            ToolsAction::Install { ref skip, dry_run } => {
-                self.install_tools(skip, dry_run).await
+            ToolsAction::Install { ref skip, dry_run, jobs, rollback } => {
+                self.install_tools(project_root, catalog, skip, dry_run, jobs, rollback).await
             }
             ToolsAction::Check { verbose } => {
-                self.check_tools(verbose).await
+                self.check_tools(project_root, &catalog, verbose).await
+            }
+            ToolsAction::Lock => {
+                self.lock_tools(project_root, &catalog)
             }
             ToolsAction::Update { ref skip } => {
-                self.update_tools(skip).await
+                self.update_tools(catalog, skip).await
+            }
+            ToolsAction::Uninstall { ref tools } => {
+                // Tools outside the manifest can still be removed
+                self.uninstall_tools(ToolCatalog::load()?, tools).await
             }
         }
     }
 
-    async fn install_tools(&self, skip: &[String], dry_run: bool) -> Result<()> {
+    async fn install_tools(
+        &self,
+        project_root: &Path,
+        catalog: ToolCatalog,
+        skip: &[String],
+        dry_run: bool,
+        jobs: usize,
+        rollback: bool,
+    ) -> Result<()> {
         println!("{}", "📦 Installing modern CLI tools...\n".bright_blue());
+        self.print_manifest_note(project_root);
 
         if dry_run {
             println!("{}", "🔍 Dry run mode - no changes will be made\n".yellow());
         }
 
-        let installer = ToolsInstaller::new();
-        installer.install_all_with_options(&skip, dry_run).await?;
+        let installer = ToolsInstaller::new(catalog)?.with_lock(ToolsLock::load(project_root)?);
+        let mut summary = installer.install_all_with_options(skip, dry_run, jobs).await?;
+        if rollback && summary.failed() > 0 {
+            println!("\n{}", "↩ Rolling back the tools installed in this run...".yellow());
+            installer.rollback(&mut summary).await?;
+        }
+        summary.print();
 
         if !dry_run {
+            installer.print_path_guidance(&summary);
+            if summary.failed() > 0 {
+                bail!("{} tool(s) failed to install; their logs are listed above", summary.failed());
+            }
+
             println!("\n{}", "✓ Installation complete!".green());
-            self.print_next_steps();
+
+            // The first install in a project with a manifest pins what it resolved to
+            let has_manifest = project_root.join(MANIFEST_FILE).exists();
+            if has_manifest && !project_root.join(LOCK_FILE).exists() {
+                let statuses = ToolsChecker::new().check_versions(installer.catalog(), None)?;
+                match ToolsLock::resolve(&statuses) {
+                    Ok(lock) => {
+                        lock.save(project_root)?;
+                        println!("✓ Locked tool versions in {}", LOCK_FILE.bright_yellow());
+                    }
+                    Err(e) => println!("{} {}", "⚠".yellow(), e),
+                }
+            }
+
+            self.print_next_steps(installer.catalog());
         }
 
         Ok(())
     }
 
-    async fn check_tools(&self, verbose: bool) -> Result<()> {
+    fn lock_tools(&self, project_root: &Path, catalog: &ToolCatalog) -> Result<()> {
+        if ToolsManifest::load(project_root)?.is_none() {
+            bail!("No {} found. List the project's tools there first.", MANIFEST_FILE);
+        }
+
+        let statuses = ToolsChecker::new().check_versions(catalog, None)?;
+        let lock = ToolsLock::resolve(&statuses)?;
+        lock.save(project_root)?;
+
+        for (tool, version) in &lock.tools {
+            println!("  {} {}", tool.bright_yellow(), version);
+        }
+        println!("\n✓ Locked {} tool(s) in {}", lock.tools.len(), LOCK_FILE.bright_yellow());
+
+        Ok(())
+    }
+
+    fn print_manifest_note(&self, project_root: &Path) {
+        if project_root.join(MANIFEST_FILE).exists() {
+            println!("{}\n", format!("Using the tools listed in {}", MANIFEST_FILE).dimmed());
+        }
+    }
+
+    async fn check_tools(&self, project_root: &Path, catalog: &ToolCatalog, verbose: bool) -> Result<()> {
         println!("{}", "🔍 Checking modern CLI tools...\n".bright_blue());
+        self.print_manifest_note(project_root);
 
         let checker = ToolsChecker::new();
-        let results = checker.check_all()?;
+        let lock = ToolsLock::load(project_root)?;
+        let results = checker.check_versions(catalog, lock.as_ref())?;
 
         let mut all_installed = true;
+        let mut unsatisfied = Vec::new();
 
-        for (tool, installed) in &results {
-            let status = if *installed {
+        for result in &results {
+            let status = if result.installed && result.satisfied() {
                 "✓".green().to_string()
             } else {
                 "✗".red().to_string()
             };
 
-            print!("{} {} ", status, tool.bright_yellow());
+            print!("{} {} ", status, result.spec.binary.bright_yellow());
 
-            if *installed && verbose {
-                if let Some(version) = checker.get_version(tool)? {
-                    print!("({})", version.dimmed());
+            let constrained = result.is_constrained();
+            if result.installed && (verbose || constrained) {
+                match &result.version {
+                    Some(version) => print!("{} ", version),
+                    None => print!("{} ", "version unknown".dimmed()),
                 }
+            } else if !result.installed && constrained {
+                print!("{} ", "missing".red());
+            }
+            match (&result.locked, result.required.is_any()) {
+                (Some(locked), true) => print!("{}", format!("(locked {})", locked).dimmed()),
+                (Some(locked), false) => print!("{}", format!("(requires {}, locked {})", result.required, locked).dimmed()),
+                (None, false) => print!("{}", format!("(requires {})", result.required).dimmed()),
+                (None, true) => {}
             }
 
             println!();
 
-            if !installed {
+            if !result.installed {
                 all_installed = false;
             }
+            if !result.satisfied() {
+                unsatisfied.push(result.spec.binary.as_str());
+            }
+        }
+
+        if verbose {
+            let managers: Vec<String> = PackageManagers::detect().available().map(|manager| manager.to_string()).collect();
+            println!("\nPackage managers: {}", if managers.is_empty() { "none found".to_string() } else { managers.join(", ") });
+            println!("Add tools in {}", forge_config_dir()?.join(CATALOG_FILE).display().to_string().dimmed());
         }
 
-        if all_installed {
+        if all_installed && unsatisfied.is_empty() {
             println!("\n{}", "🎉 All tools are installed!".green().bold());
+        } else if all_installed {
+            println!("\n{}", "⚠ Some tools do not match their required or locked versions.".yellow());
+            println!("Run {} to upgrade them.", "claude-forge tools update".bright_yellow());
         } else {
             println!("\n{}", "⚠ Some tools are missing.".yellow());
             println!("Run {} to install them.", "claude-forge tools install".bright_yellow());
         }
 
+        if !unsatisfied.is_empty() {
+            bail!("Version requirements not met: {}", unsatisfied.join(", "));
+        }
+
         Ok(())
     }
 
-    async fn update_tools(&self, skip: &[String]) -> Result<()> {
+    async fn update_tools(&self, catalog: ToolCatalog, skip: &[String]) -> Result<()> {
         println!("{}", "🔄 Updating modern CLI tools...\n".bright_blue());
 
-        let installer = ToolsInstaller::new();
-        installer.update_all_with_options(&skip).await?;
+        let installer = ToolsInstaller::new(catalog)?;
+        installer.update_all_with_options(skip).await?;
 
         println!("\n{}", "✓ Update complete!".green());
 
         Ok(())
     }
 
-    fn print_next_steps(&self) {
+    async fn uninstall_tools(&self, catalog: ToolCatalog, tools: &[String]) -> Result<()> {
+        println!("{}", "🗑  Uninstalling CLI tools...\n".bright_blue());
+
+        let installer = ToolsInstaller::new(catalog)?;
+        installer.uninstall(tools).await
+    }
+
+    fn print_next_steps(&self, catalog: &ToolCatalog) {
         println!("\n{}", "📝 Next steps:".bright_cyan().bold());
         println!("  1. Run {} to verify installation", "claude-forge tools check -v".bright_yellow());
         println!("  2. Add the tools to your PATH if needed");
         println!("  3. Restart your terminal to use the new tools");
 
         println!("\n{}", "💡 Quick start:".bright_cyan().bold());
-        println!("  • Search files: {}", "rg 'pattern'".bright_yellow());
-        println!("  • Find files: {}", "fd 'filename'".bright_yellow());
-        println!("  • View files: {}", "bat file.txt".bright_yellow());
-        println!("  • List files: {}", "eza -la --icons".bright_yellow());
-        println!("  • Disk usage: {}", "dust".bright_yellow());
+        for guidance in catalog.tools.iter().filter_map(|tool| tool.guidance.as_ref()) {
+            println!("  • {}: {}", guidance.title, guidance.usage.bright_yellow());
+        }
     }
 }
@@ -5,9 +5,10 @@ use serde::Deserialize;
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::templates::layer::user_templates_dir;
+
 const GITHUB_REPO: &str = "Casear/claude-forge";
 const GITHUB_API_URL: &str = "https://api.github.com/repos";
-const TEMPLATES_ARCHIVE: &str = "templates.tar.gz";
 
 /// Update templates to the latest version
 #[derive(Args)]
@@ -32,7 +33,6 @@ pub struct UpdateCommand {
 #[derive(Debug, Deserialize)]
 struct Release {
     tag_name: String,
-    name: String,
     body: Option<String>,
 }
 
@@ -197,7 +197,7 @@ impl UpdateCommand {
         std::fs::create_dir_all(&extract_dir)?;
 
         let status = Command::new("tar")
-            .args(&[
+            .args([
                 "xzf",
                 tar_path.to_str().unwrap(),
                 "-C",
@@ -240,10 +240,7 @@ impl UpdateCommand {
     }
 
     fn get_templates_dir(&self) -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .context("Failed to get config directory")?;
-
-        Ok(config_dir.join("claude-forge").join("templates"))
+        user_templates_dir()
     }
 
     fn copy_dir_recursive(&self, src: &std::path::Path, dst: &std::path::Path) -> Result<()> {
@@ -26,10 +26,7 @@ pub struct UpgradeCommand {
 #[derive(Debug, Deserialize)]
 struct Release {
     tag_name: String,
-    name: String,
     body: Option<String>,
-    prerelease: bool,
-    published_at: String,
 }
 
 impl UpgradeCommand {
@@ -165,7 +162,7 @@ impl UpgradeCommand {
         std::fs::create_dir_all(&extract_dir)?;
 
         let status = Command::new("tar")
-            .args(&["xzf", tar_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap()])
+            .args(["xzf", tar_path.to_str().unwrap(), "-C", extract_dir.to_str().unwrap()])
             .status()
             .context("Failed to extract archive")?;
 
@@ -186,7 +183,7 @@ impl UpgradeCommand {
             println!("Installing to {} (requires sudo)", install_path.display().to_string().bright_yellow());
 
             let status = Command::new("sudo")
-                .args(&["mv", binary_path.to_str().unwrap(), install_path.to_str().unwrap()])
+                .args(["mv", binary_path.to_str().unwrap(), install_path.to_str().unwrap()])
                 .status()?;
 
             if !status.success() {
@@ -195,7 +192,7 @@ impl UpgradeCommand {
 
             // Set permissions
             let _ = Command::new("sudo")
-                .args(&["chmod", "+x", install_path.to_str().unwrap()])
+                .args(["chmod", "+x", install_path.to_str().unwrap()])
                 .status();
         } else {
             std::fs::copy(&binary_path, &install_path)?;
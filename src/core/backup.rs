@@ -0,0 +1,181 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::lock::lock_path;
+use crate::utils::fs::{ensure_directory, read_file, write_file};
+
+/// Backups of files replaced by `init`, relative to the project root
+pub const BACKUPS_DIR: &str = ".claude/.forge/backups";
+
+const MANIFEST_FILE: &str = "backup.toml";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BackupManifest {
+    /// Files that did not exist before; restoring deletes them
+    #[serde(default)]
+    created: Vec<String>,
+    /// Files copied into the backup before being replaced
+    #[serde(default)]
+    saved: Vec<String>,
+}
+
+/// A timestamped snapshot of the files a command is about to change
+pub struct Backup {
+    pub id: String,
+    dir: PathBuf,
+    manifest: BackupManifest,
+}
+
+/// A backup available to `restore`
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub id: String,
+    pub created: usize,
+    pub saved: usize,
+}
+
+impl Backup {
+    /// Start a new backup named after the current time
+    pub fn start(project_root: &Path) -> Self {
+        let backups = project_root.join(BACKUPS_DIR);
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+
+        let mut id = timestamp.clone();
+        let mut suffix = 1;
+        while backups.join(&id).exists() {
+            id = format!("{}-{}", timestamp, suffix);
+            suffix += 1;
+        }
+
+        Self {
+            dir: backups.join(&id),
+            id,
+            manifest: BackupManifest::default(),
+        }
+    }
+
+    /// Remember the current state of `path` (relative to the project root) before it is changed
+    pub fn save(&mut self, project_root: &Path, path: &Path) -> Result<()> {
+        let key = lock_path(path);
+        if self.manifest.saved.contains(&key) || self.manifest.created.contains(&key) {
+            return Ok(());
+        }
+
+        let source = project_root.join(path);
+        if source.is_file() {
+            let dest = self.dir.join(path);
+            ensure_directory(dest.parent().unwrap())?;
+            std::fs::copy(&source, &dest)
+                .context(format!("Failed to back up {}", path.display()))?;
+            self.manifest.saved.push(key);
+        } else {
+            self.manifest.created.push(key);
+        }
+
+        Ok(())
+    }
+
+    /// Write the backup manifest. Returns `false` if nothing was recorded.
+    pub fn finish(self) -> Result<bool> {
+        if self.manifest.saved.is_empty() && self.manifest.created.is_empty() {
+            return Ok(false);
+        }
+
+        write_file(&self.dir.join(MANIFEST_FILE), &toml::to_string_pretty(&self.manifest)?)?;
+        Ok(true)
+    }
+
+    /// Backups in the project, newest first
+    pub fn list(project_root: &Path) -> Result<Vec<BackupInfo>> {
+        let backups = project_root.join(BACKUPS_DIR);
+        if !backups.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut infos = Vec::new();
+        for entry in std::fs::read_dir(&backups)?.flatten() {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let Ok(manifest) = load_manifest(&entry.path()) else { continue };
+
+            infos.push(BackupInfo {
+                id,
+                created: manifest.created.len(),
+                saved: manifest.saved.len(),
+            });
+        }
+
+        infos.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(infos)
+    }
+
+    /// Put every saved file back, delete the files created since, and remove the backup.
+    /// Returns the paths that were restored or deleted.
+    pub fn restore(project_root: &Path, id: &str) -> Result<Vec<String>> {
+        let dir = project_root.join(BACKUPS_DIR).join(id);
+        if id.contains(['/', '\\']) || !dir.is_dir() {
+            bail!("Backup not found: {}", id);
+        }
+
+        let manifest = load_manifest(&dir)?;
+        let mut changed = Vec::new();
+
+        for path in &manifest.created {
+            let target = project_root.join(path);
+            if target.is_file() {
+                std::fs::remove_file(&target)
+                    .context(format!("Failed to remove {}", path))?;
+                changed.push(path.clone());
+            }
+        }
+
+        for path in &manifest.saved {
+            let target = project_root.join(path);
+            ensure_directory(target.parent().unwrap())?;
+            std::fs::copy(dir.join(path), &target)
+                .context(format!("Failed to restore {}", path))?;
+            changed.push(path.clone());
+        }
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(changed)
+    }
+}
+
+fn load_manifest(dir: &Path) -> Result<BackupManifest> {
+    toml::from_str(&read_file(&dir.join(MANIFEST_FILE))?)
+        .context(format!("Invalid backup manifest in {}", dir.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_restore_rolls_back_changes() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let edited = Path::new(".claude/CLAUDE.md");
+        let created = Path::new(".claude/agents/new.md");
+        write_file(&root.join(edited), "mine").unwrap();
+
+        let mut backup = Backup::start(root);
+        backup.save(root, edited).unwrap();
+        backup.save(root, created).unwrap();
+        let id = backup.id.clone();
+        assert!(backup.finish().unwrap());
+
+        write_file(&root.join(edited), "generated").unwrap();
+        write_file(&root.join(created), "generated").unwrap();
+
+        let backups = Backup::list(root).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!((backups[0].saved, backups[0].created), (1, 1));
+
+        Backup::restore(root, &id).unwrap();
+        assert_eq!(read_file(&root.join(edited)).unwrap(), "mine");
+        assert!(!root.join(created).exists());
+        assert!(Backup::list(root).unwrap().is_empty());
+    }
+}
@@ -1,54 +1,223 @@
 use anyhow::{anyhow, Result};
-use std::path::Path;
+use ignore::{DirEntry, WalkBuilder};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use super::frameworks::{detect_frameworks, python_dependencies, Framework};
+use super::scripts::detect_scripts;
+use super::language::DefaultCommands;
 use super::Language;
 
 pub struct ProjectDetector;
 
+/// How deep below the root `detect_projects` looks for sub-projects
+const MAX_PROJECT_DEPTH: usize = 4;
+
+/// Dependency and build output directories that are never sub-projects, even when not gitignored
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build", "_build", "deps"];
+
+/// Share of the confidence score from marker files, source file count and lines of code
+const MARKER_WEIGHT: f64 = 0.4;
+const FILES_WEIGHT: f64 = 0.3;
+const LINES_WEIGHT: f64 = 0.3;
+
+/// Source files larger than this are counted, but their lines are not
+const MAX_COUNTED_FILE_SIZE: u64 = 1024 * 1024;
+
+/// How likely the project is written in a language, and why
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageScore {
+    pub language: Language,
+    /// Between 0 and 1
+    pub confidence: f64,
+    /// Marker files found at the project root
+    pub markers: Vec<String>,
+    pub files: usize,
+    pub lines: usize,
+    /// Frameworks found in the language's manifests
+    pub frameworks: Vec<Framework>,
+    /// Human-readable reasons for the score
+    pub evidence: Vec<String>,
+}
+
+/// Every language with evidence in the project, most likely first
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectionReport {
+    pub scores: Vec<LanguageScore>,
+    /// Source files of any supported language
+    pub total_files: usize,
+    pub total_lines: usize,
+}
+
+/// A directory with its own build manifest, e.g. a workspace member
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubProject {
+    /// Path relative to the detection root; empty for the root itself
+    pub path: PathBuf,
+    pub language: Language,
+}
+
+/// Commands used to build and check a project, where they could be determined
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectCommands {
+    pub build: Option<String>,
+    pub test: Option<String>,
+    pub lint: Option<String>,
+    pub format: Option<String>,
+    pub typecheck: Option<String>,
+    pub run: Option<String>,
+}
+
 impl ProjectDetector {
     pub fn new() -> Self {
         Self
     }
 
+    /// The most likely language of the project, see [`ProjectDetector::score`]
     pub fn detect(&self, path: &Path) -> Result<Language> {
-        // Priority order for detection
-        let languages = vec![
-            Language::Rust,
-            Language::Go,
-            Language::TypeScript,
-            Language::JavaScript,
-            Language::Python,
-            Language::Java,
-            Language::Elixir,
-            Language::Erlang,
-        ];
-
-        for lang in languages {
-            if self.has_marker_files(path, &lang) {
-                // Additional verification for TypeScript vs JavaScript
-                if lang == Language::JavaScript {
-                    if self.is_typescript_project(path) {
-                        return Ok(Language::TypeScript);
-                    }
-                }
-                return Ok(lang);
+        self.score(path)
+            .scores
+            .first()
+            .map(|score| score.language)
+            .ok_or_else(|| anyhow!("Could not detect project language"))
+    }
+
+    /// Score every language by its marker files, and by its share of the source files and
+    /// lines of code in the tree. Languages with no evidence are left out.
+    pub fn score(&self, path: &Path) -> DetectionReport {
+        let mut counts = SourceCounts::default();
+        let walker = WalkBuilder::new(path)
+            .require_git(false)
+            .filter_entry(|entry| !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+            .build();
+        for entry in walker.flatten() {
+            counts.add(&entry);
+        }
+
+        self.score_counts(path, &counts)
+    }
+
+    /// Score the project at `path` from source counts already taken
+    fn score_counts(&self, path: &Path, counts: &SourceCounts) -> DetectionReport {
+        let SourceCounts { files, lines } = counts;
+        let total_files: usize = files.values().sum();
+        let total_lines: usize = lines.values().sum();
+        let is_typescript = self.is_typescript_project(path);
+
+        let mut scores = Vec::new();
+        for (priority, language) in Language::all().enumerate() {
+            let markers: Vec<String> = language
+                .marker_files()
+                .iter()
+                // package.json marks a TypeScript or a JavaScript project, not both
+                .filter(|marker| match (language, **marker) {
+                    (Language::TypeScript, "package.json") => is_typescript,
+                    (Language::JavaScript, "package.json") => !is_typescript,
+                    _ => true,
+                })
+                .filter_map(|marker| find_marker(path, marker))
+                .collect();
+            let file_count = files.get(&language).copied().unwrap_or(0);
+            let line_count = lines.get(&language).copied().unwrap_or(0);
+            if markers.is_empty() && file_count == 0 {
+                continue;
             }
+
+            let mut confidence = 0.0;
+            let mut evidence = Vec::new();
+            if !markers.is_empty() {
+                confidence += MARKER_WEIGHT;
+                evidence.push(format!("marker files: {}", markers.join(", ")));
+            }
+            if file_count > 0 {
+                confidence += FILES_WEIGHT * file_count as f64 / total_files as f64;
+                evidence.push(format!("{} of {} source files", file_count, total_files));
+            }
+            if line_count > 0 {
+                confidence += LINES_WEIGHT * line_count as f64 / total_lines as f64;
+                evidence.push(format!("{} of {} lines of code", line_count, total_lines));
+            }
+
+            scores.push((
+                priority,
+                LanguageScore {
+                    language,
+                    confidence,
+                    markers,
+                    files: file_count,
+                    lines: line_count,
+                    frameworks: detect_frameworks(path, language),
+                    evidence,
+                },
+            ));
         }
 
-        Err(anyhow!("Could not detect project language"))
+        // Highest confidence first; ties go to the language earlier in the priority order
+        scores.sort_by(|(pa, a), (pb, b)| b.confidence.total_cmp(&a.confidence).then(pa.cmp(pb)));
+
+        DetectionReport {
+            scores: scores.into_iter().map(|(_, score)| score).collect(),
+            total_files,
+            total_lines,
+        }
     }
 
-    fn has_marker_files(&self, path: &Path, language: &Language) -> bool {
-        let markers = language.marker_files();
+    /// Every directory in the tree that is a project of its own, including the root,
+    /// sorted by path. Gitignored and hidden directories are skipped.
+    ///
+    /// The tree is walked once, and each source file counts towards the nearest project
+    /// above it, so nested packages are not scored again for every ancestor.
+    pub fn detect_projects(&self, root: &Path) -> Vec<SubProject> {
+        let walker = WalkBuilder::new(root)
+            .require_git(false)
+            .filter_entry(|entry| !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+            .build();
+
+        // Directories are visited before their contents, so a file's project is already known
+        let mut projects: HashMap<PathBuf, SourceCounts> = HashMap::new();
+        for entry in walker.flatten() {
+            if entry.file_type().is_some_and(|file_type| file_type.is_dir()) {
+                let is_project = entry.depth() <= MAX_PROJECT_DEPTH
+                    && Language::all().any(|language| self.has_marker_files(entry.path(), language));
+                if is_project {
+                    projects.insert(entry.path().to_path_buf(), SourceCounts::default());
+                }
+                continue;
+            }
 
-        for marker in markers {
-            let marker_path = path.join(marker);
-            if marker_path.exists() {
-                return true;
+            let nearest = entry
+                .path()
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| dir.starts_with(root))
+                .find(|dir| projects.contains_key(*dir))
+                .map(Path::to_path_buf);
+            if let Some(dir) = nearest {
+                projects.get_mut(&dir).unwrap().add(&entry);
             }
         }
 
-        false
+        let mut detected: Vec<SubProject> = projects
+            .iter()
+            .filter_map(|(path, counts)| {
+                let language = self.score_counts(path, counts).scores.first()?.language;
+                Some(SubProject {
+                    path: path.strip_prefix(root).unwrap_or(path).to_path_buf(),
+                    language,
+                })
+            })
+            .collect();
+
+        detected.sort_by(|a, b| a.path.cmp(&b.path));
+        detected
+    }
+
+    fn has_marker_files(&self, path: &Path, language: Language) -> bool {
+        language
+            .marker_files()
+            .iter()
+            .any(|marker| find_marker(path, marker).is_some())
     }
 
     fn is_typescript_project(&self, path: &Path) -> bool {
@@ -80,37 +249,270 @@ impl ProjectDetector {
         false
     }
 
-    pub fn detect_framework(&self, path: &Path, _language: Language) -> Option<String> {
-        // Try to detect common frameworks
-        let package_json_path = path.join("package.json");
-        if package_json_path.exists() {
-            if let Ok(content) = std::fs::read_to_string(&package_json_path) {
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                    // Check for popular frameworks
-                    let frameworks = vec![
-                        ("next", "Next.js"),
-                        ("react", "React"),
-                        ("vue", "Vue"),
-                        ("express", "Express"),
-                        ("fastify", "Fastify"),
-                        ("nestjs", "NestJS"),
-                    ];
-
-                    for (dep, framework) in frameworks {
-                        if let Some(deps) = json.get("dependencies") {
-                            if deps.get(dep).is_some() {
-                                return Some(framework.to_string());
-                            }
-                        }
+    /// Display name of the most specific framework the project uses
+    pub fn detect_framework(&self, path: &Path, language: Language) -> Option<String> {
+        detect_frameworks(path, language)
+            .first()
+            .map(|framework| framework.name.to_string())
+    }
+
+    /// Project name from the first manifest that declares one, falling back to the directory name
+    pub fn detect_project_name(&self, path: &Path) -> String {
+        let from_manifest = read_toml(&path.join("Cargo.toml"))
+            .and_then(|toml| toml.get("package")?.get("name")?.as_str().map(|s| s.to_string()))
+            .or_else(|| {
+                read_json(&path.join("package.json"))
+                    .and_then(|json| json.get("name")?.as_str().map(|s| s.to_string()))
+            })
+            .or_else(|| {
+                read_toml(&path.join("pyproject.toml")).and_then(|toml| {
+                    let name = toml
+                        .get("project")
+                        .and_then(|p| p.get("name"))
+                        .or_else(|| toml.get("tool")?.get("poetry")?.get("name"))?;
+                    name.as_str().map(|s| s.to_string())
+                })
+            })
+            .or_else(|| {
+                std::fs::read_to_string(path.join("go.mod")).ok().and_then(|content| {
+                    content
+                        .lines()
+                        .find_map(|line| line.strip_prefix("module "))
+                        .map(|module| module.trim().rsplit('/').next().unwrap_or(module).to_string())
+                })
+            });
+
+        from_manifest.unwrap_or_else(|| {
+            path.canonicalize()
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "project".to_string())
+        })
+    }
+
+    /// Package manager, determined from lock files and build files
+    pub fn detect_package_manager(&self, path: &Path, language: Language) -> Option<String> {
+        let manager = language
+            .info()
+            .package_managers
+            .iter()
+            .find(|(marker, _)| find_marker(path, marker).is_some())
+            .map(|(_, manager)| manager.to_string())?;
+
+        // Flutter apps share pubspec.yaml with plain Dart packages
+        let is_flutter = language == Language::Dart
+            && std::fs::read_to_string(path.join("pubspec.yaml")).is_ok_and(|pubspec| pubspec.contains("sdk: flutter"));
+        Some(if is_flutter { "flutter".to_string() } else { manager })
+    }
+
+    /// Build, test and lint commands for the project. Tasks the repo defines for them
+    /// (package.json scripts, Makefile targets, justfile recipes, cargo aliases) win over
+    /// the language's usual commands.
+    pub fn detect_commands(&self, path: &Path, language: Language, package_manager: Option<&str>) -> ProjectCommands {
+        let mut commands = self.default_commands(path, language, package_manager);
+
+        let scripts = detect_scripts(path, package_manager);
+        let task = |names: &[&str]| {
+            scripts
+                .iter()
+                .filter(|script| !matches!(script.source.as_str(), "tox.ini" | "noxfile.py"))
+                .find(|script| names.contains(&script.name.as_str()))
+                .map(|script| script.command.clone())
+        };
+
+        commands.build = task(&["build"]).or(commands.build);
+        commands.test = task(&["test", "tests"]).or(commands.test);
+        commands.lint = task(&["lint"]).or(commands.lint);
+        commands.format = task(&["format", "fmt"]).or(commands.format);
+        commands.typecheck = task(&["typecheck", "type-check"]).or(commands.typecheck);
+        commands.run = task(&["dev", "start", "run", "serve"]).or(commands.run);
+        commands
+    }
+
+    /// The commands a project in `language` usually uses
+    fn default_commands(&self, path: &Path, language: Language, package_manager: Option<&str>) -> ProjectCommands {
+        let cmd = |s: &str| Some(s.to_string());
+
+        match language {
+            Language::TypeScript | Language::JavaScript => {
+                let pm = package_manager.unwrap_or("npm");
+                ProjectCommands {
+                    build: None,
+                    test: Some(format!("{} test", pm)),
+                    lint: cmd("npx eslint ."),
+                    format: cmd("npx prettier --write ."),
+                    typecheck: if language == Language::TypeScript { cmd("npx tsc --noEmit") } else { None },
+                    run: None,
+                }
+            }
+            Language::Python => {
+                let prefix = match package_manager {
+                    Some("poetry") => "poetry run ",
+                    Some("uv") => "uv run ",
+                    _ => "",
+                };
+                let with_prefix = |s: &str| Some(format!("{}{}", prefix, s));
+
+                // Linters, formatters and type checkers the project is configured for
+                let dependencies: Vec<String> = python_dependencies(path).into_iter().map(|(name, _)| name).collect();
+                let tools = read_toml(&path.join("pyproject.toml"))
+                    .and_then(|pyproject| pyproject.get("tool").cloned())
+                    .unwrap_or(toml::Value::Table(Default::default()));
+                let uses = |tool: &str, config_files: &[&str]| {
+                    dependencies.iter().any(|name| name == tool)
+                        || tools.get(tool).is_some()
+                        || config_files.iter().any(|file| path.join(file).exists())
+                };
+                let ruff = uses("ruff", &["ruff.toml", ".ruff.toml"]);
+
+                ProjectCommands {
+                    build: None,
+                    test: with_prefix("pytest"),
+                    lint: if ruff {
+                        with_prefix("ruff check .")
+                    } else if uses("flake8", &[".flake8"]) {
+                        with_prefix("flake8")
+                    } else {
+                        None
+                    },
+                    format: if uses("black", &[]) {
+                        with_prefix("black .")
+                    } else if ruff {
+                        with_prefix("ruff format .")
+                    } else {
+                        None
+                    },
+                    typecheck: if uses("mypy", &["mypy.ini", ".mypy.ini"]) {
+                        with_prefix("mypy .")
+                    } else if uses("pyright", &["pyrightconfig.json"]) {
+                        with_prefix("pyright")
+                    } else {
+                        None
+                    },
+                    run: None,
+                }
+            }
+            Language::Java | Language::Kotlin => match package_manager {
+                Some("gradle") => {
+                    let gradle = if path.join("gradlew").exists() { "./gradlew" } else { "gradle" };
+                    ProjectCommands {
+                        build: Some(format!("{} build", gradle)),
+                        test: Some(format!("{} test", gradle)),
+                        lint: Some(format!("{} check", gradle)),
+                        ..Default::default()
                     }
                 }
+                _ => {
+                    let mvn = if path.join("mvnw").exists() { "./mvnw" } else { "mvn" };
+                    ProjectCommands {
+                        build: Some(format!("{} package", mvn)),
+                        test: Some(format!("{} test", mvn)),
+                        lint: Some(format!("{} verify", mvn)),
+                        ..Default::default()
+                    }
+                }
+            },
+            Language::Scala if package_manager == Some("mill") => ProjectCommands {
+                build: cmd("mill __.compile"),
+                test: cmd("mill __.test"),
+                ..Default::default()
+            },
+            Language::Cpp if path.join("meson.build").exists() && !path.join("CMakeLists.txt").exists() => {
+                ProjectCommands {
+                    build: cmd("meson compile -C build"),
+                    test: cmd("meson test -C build"),
+                    ..Default::default()
+                }
             }
+            Language::Ruby if path.join("spec").is_dir() => ProjectCommands {
+                test: cmd("bundle exec rspec"),
+                ..ProjectCommands::from(&language.info().commands)
+            },
+            Language::Dart if package_manager == Some("flutter") => ProjectCommands {
+                test: cmd("flutter test"),
+                lint: cmd("flutter analyze"),
+                format: cmd("dart format ."),
+                run: cmd("flutter run"),
+                ..Default::default()
+            },
+            _ => ProjectCommands::from(&language.info().commands),
+        }
+    }
+}
+
+impl From<&DefaultCommands> for ProjectCommands {
+    fn from(commands: &DefaultCommands) -> Self {
+        let owned = |command: Option<&str>| command.map(str::to_string);
+        Self {
+            build: owned(commands.build),
+            test: owned(commands.test),
+            lint: owned(commands.lint),
+            format: owned(commands.format),
+            typecheck: owned(commands.typecheck),
+            run: owned(commands.run),
+        }
+    }
+}
+
+/// Source files and lines of code per language
+#[derive(Debug, Default)]
+struct SourceCounts {
+    files: HashMap<Language, usize>,
+    lines: HashMap<Language, usize>,
+}
+
+impl SourceCounts {
+    /// Count `entry` if it is a source file of a supported language
+    fn add(&mut self, entry: &DirEntry) {
+        // Gradle build scripts are configuration, whatever the project is written in
+        if entry.file_name().to_string_lossy().ends_with(".gradle.kts") {
+            return;
         }
 
-        None
+        let Some(extension) = entry.path().extension().and_then(|ext| ext.to_str()) else { return };
+        let Some(language) = Language::all().find(|language| language.file_extensions().contains(&extension)) else {
+            return;
+        };
+        if !entry.file_type().is_some_and(|file_type| file_type.is_file()) {
+            return;
+        }
+
+        *self.files.entry(language).or_insert(0) += 1;
+
+        let small = entry.metadata().is_ok_and(|metadata| metadata.len() <= MAX_COUNTED_FILE_SIZE);
+        if small {
+            if let Ok(content) = std::fs::read_to_string(entry.path()) {
+                let count = content.lines().filter(|line| !line.trim().is_empty()).count();
+                *self.lines.entry(language).or_insert(0) += count;
+            }
+        }
     }
 }
 
+/// Name of the file in `dir` that `marker` refers to, where `*.ext` matches any file with that extension
+fn find_marker(dir: &Path, marker: &str) -> Option<String> {
+    let Some(extension) = marker.strip_prefix("*.") else {
+        return dir.join(marker).exists().then(|| marker.to_string());
+    };
+
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.strip_suffix(extension).is_some_and(|stem| stem.len() > 1 && stem.ends_with('.')))
+        .min()
+}
+
+pub(super) fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub(super) fn read_toml(path: &Path) -> Option<toml::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +541,188 @@ mod tests {
 
         assert_eq!(result, Language::TypeScript);
     }
+
+    #[test]
+    fn test_detect_languages_from_the_language_table() {
+        let detector = ProjectDetector::new();
+        let project = |files: &[(&str, &str)]| {
+            let temp_dir = TempDir::new().unwrap();
+            for (file, content) in files {
+                let path = temp_dir.path().join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+            }
+            temp_dir
+        };
+
+        let csharp = project(&[("Api.csproj", "<Project />"), ("Program.cs", "Console.WriteLine();")]);
+        let report = detector.score(csharp.path());
+        assert_eq!(report.scores[0].language, Language::CSharp);
+        assert_eq!(report.scores[0].markers, vec!["Api.csproj"]);
+        let pm = detector.detect_package_manager(csharp.path(), Language::CSharp);
+        assert_eq!(pm.as_deref(), Some("dotnet"));
+        let commands = detector.detect_commands(csharp.path(), Language::CSharp, pm.as_deref());
+        assert_eq!(commands.build.as_deref(), Some("dotnet build"));
+
+        let kotlin = project(&[("build.gradle.kts", "plugins {}"), ("src/main/kotlin/App.kt", "fun main() {}")]);
+        assert_eq!(detector.detect(kotlin.path()).unwrap(), Language::Kotlin);
+        let pm = detector.detect_package_manager(kotlin.path(), Language::Kotlin);
+        let commands = detector.detect_commands(kotlin.path(), Language::Kotlin, pm.as_deref());
+        assert_eq!(commands.test.as_deref(), Some("gradle test"));
+
+        let flutter = project(&[("pubspec.yaml", "dependencies:\n  flutter:\n    sdk: flutter\n")]);
+        assert_eq!(detector.detect(flutter.path()).unwrap(), Language::Dart);
+        let pm = detector.detect_package_manager(flutter.path(), Language::Dart);
+        assert_eq!(pm.as_deref(), Some("flutter"));
+        let commands = detector.detect_commands(flutter.path(), Language::Dart, pm.as_deref());
+        assert_eq!(commands.test.as_deref(), Some("flutter test"));
+
+        for (marker, language) in [
+            ("CMakeLists.txt", Language::Cpp),
+            ("Gemfile", Language::Ruby),
+            ("composer.json", Language::Php),
+            ("Package.swift", Language::Swift),
+            ("build.sbt", Language::Scala),
+            ("build.zig", Language::Zig),
+        ] {
+            assert_eq!(detector.detect(project(&[(marker, "")]).path()).unwrap(), language);
+        }
+    }
+
+    #[test]
+    fn test_kotlin_gradle_project_is_kotlin() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/main/kotlin")).unwrap();
+        fs::write(root.join("settings.gradle.kts"), "rootProject.name = \"app\"\n").unwrap();
+        fs::write(root.join("build.gradle.kts"), "plugins {\n    kotlin(\"jvm\")\n}\n").unwrap();
+        fs::write(root.join("src/main/kotlin/App.kt"), "fun main() {}\n").unwrap();
+
+        let report = ProjectDetector::new().score(root);
+        let ranked: Vec<Language> = report.scores.iter().map(|score| score.language).collect();
+        assert_eq!(ranked, vec![Language::Kotlin]);
+    }
+
+    #[test]
+    fn test_java_project_with_kotlin_gradle_scripts_is_java() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/main/java")).unwrap();
+        fs::write(root.join("settings.gradle.kts"), "rootProject.name = \"app\"\n").unwrap();
+        fs::write(root.join("build.gradle.kts"), "plugins {\n    java\n}\n").unwrap();
+        fs::write(root.join("src/main/java/App.java"), "class App {}\n").unwrap();
+
+        assert_eq!(ProjectDetector::new().detect(root).unwrap(), Language::Java);
+    }
+
+    #[test]
+    fn test_detect_commands_from_package_json_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"name": "web-app", "scripts": {"build": "vite build", "lint": "eslint src"}}"#,
+        ).unwrap();
+        fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let detector = ProjectDetector::new();
+        let pm = detector.detect_package_manager(temp_dir.path(), Language::JavaScript);
+        assert_eq!(pm.as_deref(), Some("pnpm"));
+
+        let commands = detector.detect_commands(temp_dir.path(), Language::JavaScript, pm.as_deref());
+        assert_eq!(commands.build.as_deref(), Some("pnpm build"));
+        assert_eq!(commands.lint.as_deref(), Some("pnpm lint"));
+        assert_eq!(commands.test.as_deref(), Some("pnpm test"));
+
+        assert_eq!(detector.detect_project_name(temp_dir.path()), "web-app");
+    }
+
+    #[test]
+    fn test_detect_commands_prefers_repo_tasks_and_configured_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"svc\"\n\n[dependency-groups]\ndev = [\"mypy>=1\"]\n\n[tool.ruff]\nline-length = 100\n",
+        )
+        .unwrap();
+        fs::write(root.join("uv.lock"), "").unwrap();
+        fs::write(root.join("Makefile"), "test:\n\tuv run pytest -x\n").unwrap();
+
+        let detector = ProjectDetector::new();
+        let pm = detector.detect_package_manager(root, Language::Python);
+        let commands = detector.detect_commands(root, Language::Python, pm.as_deref());
+        assert_eq!(commands.test.as_deref(), Some("make test"));
+        assert_eq!(commands.lint.as_deref(), Some("uv run ruff check ."));
+        assert_eq!(commands.format.as_deref(), Some("uv run ruff format ."));
+        assert_eq!(commands.typecheck.as_deref(), Some("uv run mypy ."));
+    }
+
+    #[test]
+    fn test_score_prefers_source_files_over_tooling_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("pyproject.toml"), "").unwrap();
+        fs::write(root.join("package.json"), r#"{"devDependencies": {"prettier": "3"}}"#).unwrap();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(root.join("app/main.py"), "import os\n\nprint(os.name)\n").unwrap();
+        fs::write(root.join("app/util.py"), "def f():\n    return 1\n").unwrap();
+        fs::write(root.join("prettier.config.js"), "module.exports = {}\n").unwrap();
+
+        let report = ProjectDetector::new().score(root);
+        let ranked: Vec<Language> = report.scores.iter().map(|score| score.language).collect();
+        assert_eq!(ranked, vec![Language::Python, Language::JavaScript]);
+        assert_eq!((report.total_files, report.total_lines), (3, 5));
+
+        let python = &report.scores[0];
+        assert_eq!(python.markers, vec!["pyproject.toml"]);
+        assert_eq!((python.files, python.lines), (2, 4));
+        assert!(python.confidence > 0.8 && python.confidence <= 1.0);
+    }
+
+    #[test]
+    fn test_detect_projects_in_monorepo() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["backend", "web/app", "web/app/node_modules/left-pad", "generated"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("backend/Cargo.toml"), "").unwrap();
+        fs::write(root.join("web/app/package.json"), "{}").unwrap();
+        fs::write(root.join("web/app/tsconfig.json"), "{}").unwrap();
+        fs::write(root.join("web/app/node_modules/left-pad/package.json"), "{}").unwrap();
+        fs::write(root.join("generated/go.mod"), "module generated").unwrap();
+        fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+
+        let projects = ProjectDetector::new().detect_projects(root);
+        assert_eq!(
+            projects,
+            vec![
+                SubProject { path: PathBuf::from("backend"), language: Language::Rust },
+                SubProject { path: PathBuf::from("web/app"), language: Language::TypeScript },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_package_sources_count_only_towards_their_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("scripts/py")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        fs::write(root.join("scripts/py/pyproject.toml"), "").unwrap();
+        for name in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+            fs::write(root.join(format!("scripts/py/{}.py", name)), "import os\nprint(os.name)\n").unwrap();
+        }
+
+        let projects = ProjectDetector::new().detect_projects(root);
+        assert_eq!(
+            projects,
+            vec![
+                SubProject { path: PathBuf::new(), language: Language::Rust },
+                SubProject { path: PathBuf::from("scripts/py"), language: Language::Python },
+            ]
+        );
+    }
 }
@@ -0,0 +1,334 @@
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+
+use super::detector::{read_json, read_toml};
+use super::Language;
+
+/// A framework found in a project's dependencies
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Framework {
+    /// Identifier, also the name of the framework's template pack, e.g. `spring-boot`
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Where the framework was found, e.g. `django in requirements.txt`
+    pub evidence: String,
+}
+
+/// Finds the frameworks a project uses
+pub trait FrameworkDetector {
+    /// Languages whose projects this detector understands
+    fn languages(&self) -> &[Language];
+
+    /// Frameworks found in the project, most specific first
+    fn detect(&self, project_root: &Path) -> Vec<Framework>;
+}
+
+/// A dependency and the manifest that declares it
+type Dependency = (String, &'static str);
+
+/// Matches the dependencies declared in a project's manifests against known framework packages
+struct DependencyDetector {
+    languages: &'static [Language],
+    dependencies: fn(&Path) -> Vec<Dependency>,
+    /// `(package, id, name)`, most specific first. A trailing `*` matches any package with that prefix.
+    frameworks: &'static [(&'static str, &'static str, &'static str)],
+}
+
+impl FrameworkDetector for DependencyDetector {
+    fn languages(&self) -> &[Language] {
+        self.languages
+    }
+
+    fn detect(&self, project_root: &Path) -> Vec<Framework> {
+        let dependencies = (self.dependencies)(project_root);
+
+        self.frameworks
+            .iter()
+            .filter_map(|(package, id, name)| {
+                let (dependency, manifest) = dependencies.iter().find(|(dependency, _)| match package.strip_suffix('*') {
+                    Some(prefix) => dependency.starts_with(prefix),
+                    None => dependency == package,
+                })?;
+
+                Some(Framework {
+                    id,
+                    name,
+                    evidence: format!("{} in {}", dependency, manifest),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Every framework detector; add new ones here
+pub fn detectors() -> Vec<Box<dyn FrameworkDetector>> {
+    vec![
+        Box::new(DependencyDetector {
+            languages: &[Language::TypeScript, Language::JavaScript],
+            dependencies: node_dependencies,
+            frameworks: &[
+                ("next", "nextjs", "Next.js"),
+                ("@nestjs/core", "nestjs", "NestJS"),
+                ("nuxt", "nuxt", "Nuxt"),
+                ("@angular/core", "angular", "Angular"),
+                ("@sveltejs/kit", "sveltekit", "SvelteKit"),
+                ("react", "react", "React"),
+                ("vue", "vue", "Vue"),
+                ("svelte", "svelte", "Svelte"),
+                ("express", "express", "Express"),
+                ("fastify", "fastify", "Fastify"),
+            ],
+        }),
+        Box::new(DependencyDetector {
+            languages: &[Language::Python],
+            dependencies: python_dependencies,
+            frameworks: &[
+                ("django", "django", "Django"),
+                ("fastapi", "fastapi", "FastAPI"),
+                ("flask", "flask", "Flask"),
+            ],
+        }),
+        Box::new(DependencyDetector {
+            languages: &[Language::Rust],
+            dependencies: cargo_dependencies,
+            frameworks: &[
+                ("axum", "axum", "Axum"),
+                ("actix-web", "actix-web", "Actix Web"),
+                ("bevy", "bevy", "Bevy"),
+                ("tokio", "tokio", "Tokio"),
+            ],
+        }),
+        Box::new(DependencyDetector {
+            languages: &[Language::Go],
+            dependencies: go_dependencies,
+            frameworks: &[
+                ("github.com/gin-gonic/gin", "gin", "Gin"),
+                ("github.com/labstack/echo*", "echo", "Echo"),
+            ],
+        }),
+        Box::new(DependencyDetector {
+            languages: &[Language::Java],
+            dependencies: jvm_dependencies,
+            frameworks: &[
+                ("spring-boot*", "spring-boot", "Spring Boot"),
+                ("org.springframework.boot", "spring-boot", "Spring Boot"),
+            ],
+        }),
+        Box::new(DependencyDetector {
+            languages: &[Language::Elixir],
+            dependencies: mix_dependencies,
+            frameworks: &[("phoenix", "phoenix", "Phoenix")],
+        }),
+    ]
+}
+
+/// Frameworks used by a project in `language`, most specific first
+pub fn detect_frameworks(project_root: &Path, language: Language) -> Vec<Framework> {
+    let mut frameworks: Vec<Framework> = Vec::new();
+
+    for detector in detectors().iter().filter(|detector| detector.languages().contains(&language)) {
+        for framework in detector.detect(project_root) {
+            if !frameworks.iter().any(|existing| existing.id == framework.id) {
+                frameworks.push(framework);
+            }
+        }
+    }
+
+    frameworks
+}
+
+fn node_dependencies(root: &Path) -> Vec<Dependency> {
+    let Some(json) = read_json(&root.join("package.json")) else { return Vec::new() };
+
+    ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .filter_map(|section| json.get(section)?.as_object())
+        .flat_map(|deps| deps.keys().map(|name| (name.clone(), "package.json")))
+        .collect()
+}
+
+pub(super) fn python_dependencies(root: &Path) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    if let Some(pyproject) = read_toml(&root.join("pyproject.toml")) {
+        let project = pyproject.get("project");
+        let requirements = project
+            .and_then(|project| project.get("dependencies")?.as_array().cloned())
+            .into_iter()
+            .flatten()
+            .chain(
+                project
+                    .and_then(|project| project.get("optional-dependencies")?.as_table().cloned())
+                    .into_iter()
+                    .flat_map(|groups| groups.into_iter().map(|(_, deps)| deps))
+                    .filter_map(|deps| deps.as_array().cloned())
+                    .flatten(),
+            );
+        for requirement in requirements {
+            if let Some(requirement) = requirement.as_str() {
+                dependencies.push((python_package(requirement), "pyproject.toml"));
+            }
+        }
+
+        // PEP 735 dependency groups, e.g. `dev = ["ruff"]`
+        let groups = pyproject.get("dependency-groups").and_then(|groups| groups.as_table().cloned());
+        for group in groups.into_iter().flat_map(|groups| groups.into_iter().map(|(_, deps)| deps)) {
+            for requirement in group.as_array().into_iter().flatten().filter_map(|r| r.as_str()) {
+                dependencies.push((python_package(requirement), "pyproject.toml"));
+            }
+        }
+
+        // Poetry dependencies, including those in groups such as `[tool.poetry.group.dev.dependencies]`
+        let poetry = pyproject.get("tool").and_then(|tool| tool.get("poetry")).cloned();
+        let poetry_dependencies = poetry
+            .iter()
+            .filter_map(|poetry| poetry.get("dependencies"))
+            .chain(
+                poetry
+                    .iter()
+                    .filter_map(|poetry| poetry.get("group")?.as_table())
+                    .flat_map(|groups| groups.values().filter_map(|group| group.get("dependencies"))),
+            )
+            .filter_map(|deps| deps.as_table())
+            .flat_map(|deps| deps.keys())
+            .map(|name| (python_package(name), "pyproject.toml"))
+            .collect::<Vec<_>>();
+        dependencies.extend(poetry_dependencies);
+    }
+
+    for file in ["requirements.txt", "requirements-dev.txt"] {
+        let Ok(content) = std::fs::read_to_string(root.join(file)) else { continue };
+        for line in content.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') && !line.starts_with('-') {
+                dependencies.push((python_package(line), file));
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Normalized package name of a requirement such as `Django[argon2]>=4.2`
+fn python_package(requirement: &str) -> String {
+    requirement
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect::<String>()
+        .to_lowercase()
+        .replace('_', "-")
+}
+
+fn cargo_dependencies(root: &Path) -> Vec<Dependency> {
+    let Some(cargo) = read_toml(&root.join("Cargo.toml")) else { return Vec::new() };
+
+    let workspace = cargo.get("workspace").and_then(|workspace| workspace.get("dependencies"));
+    ["dependencies", "dev-dependencies"]
+        .iter()
+        .filter_map(|section| cargo.get(section))
+        .chain(workspace)
+        .filter_map(|deps| deps.as_table())
+        .flat_map(|deps| deps.keys().map(|name| (name.clone(), "Cargo.toml")))
+        .collect()
+}
+
+fn go_dependencies(root: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(root.join("go.mod")) else { return Vec::new() };
+
+    let mut dependencies = Vec::new();
+    let mut in_block = false;
+    for line in content.lines().map(str::trim) {
+        let module = if in_block {
+            if line.starts_with(')') {
+                in_block = false;
+                continue;
+            }
+            line.split_whitespace().next()
+        } else if line.starts_with("require (") {
+            in_block = true;
+            continue;
+        } else {
+            line.strip_prefix("require ").and_then(|rest| rest.split_whitespace().next())
+        };
+
+        if let Some(module) = module.filter(|module| !module.starts_with("//")) {
+            dependencies.push((module.to_string(), "go.mod"));
+        }
+    }
+
+    dependencies
+}
+
+fn jvm_dependencies(root: &Path) -> Vec<Dependency> {
+    let artifact = Regex::new(r"<artifactId>\s*([^<\s]+)\s*</artifactId>").unwrap();
+    let coordinate = Regex::new(r#"["']([\w.\-]+):([\w.\-]+)"#).unwrap();
+    let plugin = Regex::new(r#"id\s*\(?\s*["']([\w.\-]+)["']"#).unwrap();
+    let mut dependencies = Vec::new();
+
+    if let Ok(content) = std::fs::read_to_string(root.join("pom.xml")) {
+        for capture in artifact.captures_iter(&content) {
+            dependencies.push((capture[1].to_string(), "pom.xml"));
+        }
+    }
+
+    for manifest in ["build.gradle", "build.gradle.kts"] {
+        let Ok(content) = std::fs::read_to_string(root.join(manifest)) else { continue };
+        for capture in coordinate.captures_iter(&content) {
+            dependencies.push((capture[2].to_string(), manifest));
+        }
+        for capture in plugin.captures_iter(&content) {
+            dependencies.push((capture[1].to_string(), manifest));
+        }
+    }
+
+    dependencies
+}
+
+fn mix_dependencies(root: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(root.join("mix.exs")) else { return Vec::new() };
+    let dependency = Regex::new(r"\{\s*:(\w+)\s*,").unwrap();
+
+    dependency
+        .captures_iter(&content)
+        .map(|capture| (capture[1].to_string(), "mix.exs"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn ids(root: &Path, language: Language) -> Vec<&'static str> {
+        detect_frameworks(root, language).iter().map(|framework| framework.id).collect()
+    }
+
+    #[test]
+    fn test_detect_frameworks_from_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("package.json"), r#"{"dependencies": {"@nestjs/core": "10"}, "devDependencies": {"react": "18"}}"#).unwrap();
+        assert_eq!(ids(root, Language::TypeScript), vec!["nestjs", "react"]);
+
+        fs::write(root.join("pyproject.toml"), "[project]\ndependencies = [\"FastAPI[all]>=0.110\"]\n").unwrap();
+        fs::write(root.join("requirements.txt"), "# web\nDjango==5.0\n").unwrap();
+        let frameworks = detect_frameworks(root, Language::Python);
+        assert_eq!(frameworks[0].id, "django");
+        assert_eq!(frameworks[0].evidence, "django in requirements.txt");
+        assert_eq!(frameworks[1].id, "fastapi");
+
+        fs::write(root.join("Cargo.toml"), "[dependencies]\ntokio = \"1\"\naxum = \"0.7\"\n").unwrap();
+        assert_eq!(ids(root, Language::Rust), vec!["axum", "tokio"]);
+
+        fs::write(root.join("go.mod"), "module x\n\nrequire (\n\tgithub.com/labstack/echo/v4 v4.11.0\n)\n").unwrap();
+        assert_eq!(ids(root, Language::Go), vec!["echo"]);
+
+        fs::write(root.join("build.gradle.kts"), "plugins {\n  id(\"org.springframework.boot\") version \"3.2.0\"\n}\n").unwrap();
+        assert_eq!(ids(root, Language::Java), vec!["spring-boot"]);
+
+        fs::write(root.join("mix.exs"), "defp deps do\n  [{:phoenix, \"~> 1.7\"}]\nend\n").unwrap();
+        assert_eq!(ids(root, Language::Elixir), vec!["phoenix"]);
+    }
+}
@@ -1,144 +1,567 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
+use super::backup::Backup;
+use super::permissions::SuggestedPermissions;
+use super::settings::{HookCommand, Settings, SETTINGS_FILE};
+use super::lock::{content_hash, lock_path, write_base, FileStatus, ForgeLock, LockedFile, LockedPack, BASE_DIR, LOCK_FILE};
+use super::detector::{ProjectDetector, SubProject};
+use super::frameworks::detect_frameworks;
 use super::{Language, tools_detector::ToolsDetector};
+use crate::templates::context::ProjectContext;
+use crate::templates::layer::{Template, TemplateLayer};
+use crate::templates::pack::{InstallPlan, PackFile};
 use crate::templates::registry::TemplateRegistry;
-use crate::utils::fs::write_file;
+use crate::templates::renderer::TemplateRenderer;
+use crate::utils::fs::{set_executable, write_file};
+
+/// A file written from a template, and the template layer it came from
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    pub pack: String,
+    pub layer: TemplateLayer,
+    /// Path of the template within the template tree
+    pub template: String,
+    pub hash: String,
+}
+
+/// What applying a plan does to a single file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Create,
+    /// Replace a file that is unchanged since it was generated
+    Overwrite,
+    Unchanged,
+    /// Replace a file that was edited, or was not generated by claude-forge; needs `--force`
+    Modified,
+}
+
+/// A file `init` is about to write
+#[derive(Debug, Clone)]
+pub struct PlannedChange {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    pub action: ChangeAction,
+    /// Content on disk, if the file exists
+    pub current: Option<String>,
+    pub content: String,
+    pub executable: bool,
+    /// Lockfile record for files rendered from a template
+    pub generated: Option<GeneratedFile>,
+}
+
+impl PlannedChange {
+    fn new(target_path: &Path, lock: &ForgeLock, path: PathBuf, content: String) -> Self {
+        let current = std::fs::read_to_string(target_path.join(&path)).ok();
+        let action = match &current {
+            None => ChangeAction::Create,
+            Some(current) if *current == content => ChangeAction::Unchanged,
+            Some(_) if lock.status(target_path, &path) == FileStatus::Pristine => ChangeAction::Overwrite,
+            Some(_) => ChangeAction::Modified,
+        };
+
+        Self {
+            path,
+            action,
+            current,
+            content,
+            executable: false,
+            generated: None,
+        }
+    }
+}
+
+/// The outcome of [`ConfigGenerator::apply`]
+#[derive(Debug, Default)]
+pub struct AppliedChanges {
+    /// Files rendered from templates, including unchanged ones
+    pub generated: Vec<GeneratedFile>,
+    pub written: Vec<PathBuf>,
+    /// Modified files left alone because `force` was not set
+    pub skipped: Vec<PathBuf>,
+    /// Id of the backup holding the replaced files
+    pub backup: Option<String>,
+}
+
+/// Project memory file, relative to the project root
+const MEMORY_FILE: &str = ".claude/CLAUDE.md";
 
 pub struct ConfigGenerator {
-    language: Language,
     minimal: bool,
+    /// Workspace members below the project root, which get a CLAUDE.md of their own
+    packages: Vec<SubProject>,
     registry: TemplateRegistry,
+    plan: InstallPlan,
+    context: ProjectContext,
+    renderer: TemplateRenderer,
 }
 
 impl ConfigGenerator {
-    pub fn new(language: Language, minimal: bool) -> Self {
-        Self {
-            language,
+    /// Prepare to install the pack of the project's framework, or else its language. `variables` override the pack defaults.
+    pub fn new(project_root: &Path, language: Language, minimal: bool, variables: &BTreeMap<String, String>) -> Result<Self> {
+        let registry = TemplateRegistry::for_project(project_root);
+        let pack = registry.project_pack_name(language, &detect_frameworks(project_root, language));
+        Self::build(project_root, language, registry, &pack, minimal, variables)
+    }
+
+    /// Prepare to install a specific template pack and the packs it extends
+    pub fn for_pack(project_root: &Path, language: Language, pack: &str, variables: &BTreeMap<String, String>) -> Result<Self> {
+        let registry = TemplateRegistry::for_project(project_root);
+        Self::build(project_root, language, registry, pack, false, variables)
+    }
+
+    fn build(
+        project_root: &Path,
+        language: Language,
+        registry: TemplateRegistry,
+        pack: &str,
+        minimal: bool,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<Self> {
+        let context = ProjectContext::detect(project_root, language);
+        let packages = ProjectDetector::new()
+            .detect_projects(project_root)
+            .into_iter()
+            .filter(|project| !project.path.as_os_str().is_empty())
+            .collect();
+
+        Self::with_context(registry, pack, context, packages, minimal, variables)
+    }
+
+    fn with_context(
+        registry: TemplateRegistry,
+        pack: &str,
+        mut context: ProjectContext,
+        packages: Vec<SubProject>,
+        minimal: bool,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<Self> {
+        let plan = registry.install_plan(pack)?;
+        context.vars = plan.resolve_variables(variables)?;
+        let renderer = TemplateRenderer::new(&context)?;
+
+        Ok(Self {
             minimal,
-            registry: TemplateRegistry::new(),
-        }
+            packages,
+            registry,
+            plan,
+            context,
+            renderer,
+        })
+    }
+
+    pub fn plan(&self) -> &InstallPlan {
+        &self.plan
     }
 
-    pub async fn generate(&self, target_path: &Path) -> Result<()> {
-        let claude_dir = target_path.join(".claude");
+    pub fn packages(&self) -> &[SubProject] {
+        &self.packages
+    }
 
-        // Generate CLAUDE.md
-        self.generate_claude_md(&claude_dir)?;
+    /// Work out what installing the plan does to each file, without touching the project
+    pub fn plan_changes(&self, target_path: &Path) -> Result<Vec<PlannedChange>> {
+        let lock = ForgeLock::load(target_path)?;
+        let mut changes = Vec::new();
 
-        // Generate config.json (if not minimal)
-        if !self.minimal {
-            self.generate_config_json(&claude_dir)?;
-        }
+        // Pack files (only the essential ones if minimal)
+        for file in &self.plan.files {
+            if self.minimal && !file.essential {
+                continue;
+            }
 
-        // Generate .claudeignore
-        self.generate_claudeignore(&claude_dir)?;
+            let template = self.render_file(file)?;
+            let generated = GeneratedFile {
+                path: file.dest.clone(),
+                pack: file.pack.clone(),
+                layer: template.layer,
+                template: file.source.clone(),
+                hash: content_hash(&template.content),
+            };
+            changes.push(PlannedChange {
+                executable: file.executable,
+                generated: Some(generated),
+                ..PlannedChange::new(target_path, &lock, file.dest.clone(), template.content)
+            });
+        }
 
-        // Copy default agents (if not minimal)
-        if !self.minimal {
-            self.copy_default_agents(&claude_dir)?;
+        // A CLAUDE.md in each workspace member, from that member's language pack
+        for package in &self.packages {
+            if let Some(change) = self.package_change(target_path, &lock, package)? {
+                changes.push(change);
+            }
         }
 
-        // Copy default commands (if not minimal)
+        // settings.json (if not minimal). Existing settings are kept, so updating it is always safe.
         if !self.minimal {
-            self.copy_default_commands(&claude_dir)?;
+            let path = PathBuf::from(SETTINGS_FILE);
+            let mut change = PlannedChange::new(target_path, &lock, path, self.settings_json(target_path)?);
+            if change.action == ChangeAction::Modified {
+                change.action = ChangeAction::Overwrite;
+            }
+            changes.push(change);
         }
 
-        Ok(())
+        Ok(changes)
     }
 
-    fn generate_claude_md(&self, claude_dir: &Path) -> Result<()> {
-        // 獲取語言特定的模板
-        let mut content = self.registry.get_language_claude_md(self.language)?;
+    fn package_change(&self, target_path: &Path, lock: &ForgeLock, package: &SubProject) -> Result<Option<PlannedChange>> {
+        let root = target_path.join(&package.path);
+        let registry = TemplateRegistry::for_project(target_path);
+        let pack = registry.project_pack_name(package.language, &detect_frameworks(&root, package.language));
+        // Packages were found by this project's walk and share its tools, so neither is detected again
+        let context = self.context.for_package(&root, package.language);
+        let generator = Self::with_context(registry, &pack, context, Vec::new(), true, &BTreeMap::new())?;
 
-        // 檢測已安裝的工具並生成智能的工具使用說明
-        let tools_detector = ToolsDetector::new();
-        if let Ok(installed_tools) = tools_detector.detect() {
-            let tools_section = tools_detector.generate_tools_section(&installed_tools);
-
-            // 如果模板中已經有 CLI Tool Usage 部分，替換它
-            if content.contains("## 🚫 CLI Tool Usage") {
-                // 找到該部分並替換
-                if let Some(start) = content.find("## 🚫 CLI Tool Usage") {
-                    // 找到下一個 ## 標題或文件結尾
-                    let after_section = &content[start..];
-                    if let Some(next_section) = after_section[1..].find("\n## ") {
-                        let end = start + next_section + 1;
-                        content.replace_range(start..end, &tools_section);
-                    } else {
-                        // 這是最後一個部分
-                        content.replace_range(start.., &tools_section);
-                    }
+        let Some(file) = generator.plan.files.iter().rev().find(|file| file.dest == Path::new(MEMORY_FILE)) else {
+            return Ok(None);
+        };
+        let template = generator.render_with_sections(file)?;
+        let content = template.content;
+
+        let path = package.path.join("CLAUDE.md");
+        let generated = GeneratedFile {
+            path: path.clone(),
+            pack: file.pack.clone(),
+            layer: template.layer,
+            template: file.source.clone(),
+            hash: content_hash(&content),
+        };
+        Ok(Some(PlannedChange {
+            generated: Some(generated),
+            ..PlannedChange::new(target_path, lock, path, content)
+        }))
+    }
+
+    /// Write the planned changes, skipping modified files unless `force`.
+    /// Every file replaced or created is recorded in a backup that `restore` can roll back.
+    pub fn apply(&self, target_path: &Path, changes: &[PlannedChange], force: bool) -> Result<AppliedChanges> {
+        let previous = ForgeLock::load(target_path)?;
+        let mut backup = Backup::start(target_path);
+        let mut applied = AppliedChanges::default();
+
+        for change in changes {
+            if change.action == ChangeAction::Modified && !force {
+                applied.skipped.push(change.path.clone());
+                continue;
+            }
+
+            if change.action != ChangeAction::Unchanged {
+                backup.save(target_path, &change.path)?;
+                let file_path = target_path.join(&change.path);
+                write_file(&file_path, &change.content)
+                    .context(format!("Failed to create {}", change.path.display()))?;
+                if change.executable {
+                    set_executable(&file_path)?;
+                }
+                applied.written.push(change.path.clone());
+            }
+
+            if let Some(generated) = &change.generated {
+                if change.action != ChangeAction::Unchanged {
+                    backup.save(target_path, &Path::new(BASE_DIR).join(&change.path))?;
                 }
-            } else {
-                // 如果沒有，添加到最後
-                content.push_str("\n");
-                content.push_str(&tools_section);
+                write_base(target_path, &change.path, &change.content)?;
+                applied.generated.push(generated.clone());
+            }
+        }
+
+        // Files left alone keep their records, so they are still recognised as generated
+        let mut lock = ForgeLock {
+            minimal: self.minimal,
+            ..ForgeLock::default()
+        };
+        for path in &applied.skipped {
+            if let Some(locked) = previous.file(path) {
+                lock.record_file(locked.clone());
             }
+        }
+        self.record_in_lock(&mut lock, &applied.generated);
+
+        if !applied.written.is_empty() {
+            backup.save(target_path, Path::new(LOCK_FILE))?;
+        }
+        lock.save(target_path)?;
+
+        let id = backup.id.clone();
+        if backup.finish()? {
+            applied.backup = Some(id);
+        }
+
+        Ok(applied)
+    }
+
+    /// Record files installed into an existing project in its lockfile
+    pub fn update_lock(&self, target_path: &Path, generated: &[GeneratedFile]) -> Result<()> {
+        let mut lock = ForgeLock::load(target_path)?;
+        self.record_in_lock(&mut lock, generated);
+        lock.save(target_path)
+    }
+
+    fn record_in_lock(&self, lock: &mut ForgeLock, generated: &[GeneratedFile]) {
+        lock.variables.extend(self.context.vars.clone());
 
-            // 添加工具狀態摘要到文件頂部
-            let status = tools_detector.generate_tools_status(&installed_tools);
-            content = format!("<!-- {} -->\n\n{}", status, content);
-        }
-
-        let file_path = claude_dir.join("CLAUDE.md");
-        write_file(&file_path, &content)?;
-        Ok(())
-    }
-
-    fn generate_config_json(&self, claude_dir: &Path) -> Result<()> {
-        let config = serde_json::json!({
-            "version": "1.0",
-            "language": self.language.to_string().to_lowercase(),
-            "features": {
-                "sdd_workflow": true,
-                "modern_cli_tools": true
-            },
-            "hooks": {
-                "PreToolUse": {
-                    "Read": ".claude/hooks/security-check.sh",
-                },
-                "SessionStart": ".claude/hooks/session-init.sh"
+        for pack in &self.plan.packs {
+            if generated.iter().any(|file| file.pack == pack.name()) {
+                lock.record_pack(LockedPack {
+                    name: pack.name().to_string(),
+                    version: pack.manifest.pack.version.clone(),
+                });
             }
-        });
+        }
+
+        for file in generated {
+            let pack_version = self
+                .plan
+                .packs
+                .iter()
+                .find(|pack| pack.name() == file.pack)
+                .map(|pack| pack.manifest.pack.version.clone())
+                .or_else(|| self.registry.load_pack(&file.pack).ok().map(|pack| pack.manifest.pack.version))
+                .unwrap_or_default();
+
+            lock.record_file(LockedFile {
+                path: lock_path(&file.path),
+                template: file.template.clone(),
+                pack: file.pack.clone(),
+                pack_version,
+                source: file.layer.id(),
+                revision: file.layer.revision().map(str::to_string),
+                hash: file.hash.clone(),
+            });
+        }
+    }
+
+    /// Render a pack file exactly as it would be written to the project
+    pub fn render_file(&self, file: &PackFile) -> Result<Template> {
+        let mut template = self.render_with_sections(file)?;
+
+        if file.dest == Path::new(MEMORY_FILE) {
+            template.content = self.add_packages_section(template.content);
+            template.content = self.add_tools_section(template.content);
+        }
+
+        Ok(template)
+    }
 
-        let content = serde_json::to_string_pretty(&config)?;
-        let file_path = claude_dir.join("config.json");
-        write_file(&file_path, &content)?;
+    /// Render a pack file with the sections other packs in the plan append to it
+    fn render_with_sections(&self, file: &PackFile) -> Result<Template> {
+        let template = self.registry.read_template(&file.source)?;
+        let mut content = self.render_pack_file(file, template.content)?;
+
+        for section in self.plan.sections.iter().filter(|section| section.dest == file.dest) {
+            let source = self.registry.read_template(&section.source)?;
+            content.push('\n');
+            content.push_str(&self.render_pack_file(section, source.content)?);
+        }
+
+        Ok(Template {
+            content,
+            layer: template.layer,
+        })
+    }
+
+    /// Only files that opt in go through Tera, so `${#array[@]}` in a hook or `{{` in markdown survive as written
+    fn render_pack_file(&self, file: &PackFile, content: String) -> Result<String> {
+        if file.render {
+            self.renderer.render(&file.source, &content)
+        } else {
+            Ok(content)
+        }
+    }
+
+    /// Render any template content with this project's context
+    pub fn render(&self, name: &str, content: &str) -> Result<String> {
+        self.renderer.render(name, content)
+    }
+
+    pub fn install_file(&self, target_path: &Path, file: &PackFile) -> Result<GeneratedFile> {
+        let template = self.render_file(file)?;
+
+        let file_path = target_path.join(&file.dest);
+        write_file(&file_path, &template.content)
+            .context(format!("Failed to create {}", file.dest.display()))?;
+        write_base(target_path, &file.dest, &template.content)?;
+
+        if file.executable {
+            set_executable(&file_path)?;
+        }
 
-        Ok(())
+        Ok(GeneratedFile {
+            path: file.dest.clone(),
+            pack: file.pack.clone(),
+            layer: template.layer,
+            template: file.source.clone(),
+            hash: content_hash(&template.content),
+        })
     }
 
-    fn generate_claudeignore(&self, claude_dir: &Path) -> Result<()> {
-        let content = self.registry.get_claudeignore_template()?;
-        let file_path = claude_dir.parent().unwrap().join(".claudeignore");
-        write_file(&file_path, &content)?;
-        Ok(())
+    /// List the workspace members, so Claude knows each has its own CLAUDE.md
+    fn add_packages_section(&self, mut content: String) -> String {
+        if self.packages.is_empty() {
+            return content;
+        }
+
+        content.push_str("\n## Packages\n\nEach package has its own CLAUDE.md with its commands and conventions.\n\n");
+        for package in &self.packages {
+            content.push_str(&format!("- `{}/` — {}\n", lock_path(&package.path), package.language.to_string()));
+        }
+
+        content
     }
 
-    fn copy_default_agents(&self, claude_dir: &Path) -> Result<()> {
-        let agents = vec!["code-reviewer", "security-scanner"];
+    fn add_tools_section(&self, mut content: String) -> String {
+        // 根據已安裝的工具生成智能的工具使用說明
+        let tools_detector = ToolsDetector::new();
+        let installed_tools = &self.context.tools;
+        let tools_section = tools_detector.generate_tools_section(installed_tools);
 
-        for agent in agents {
-            let content = self.registry.get_default_agent_template(agent)?;
-            let file_path = claude_dir.join("agents").join(format!("{}.md", agent));
-            write_file(&file_path, &content)
-                .context(format!("Failed to create agent: {}", agent))?;
+        // 如果模板中已經有 CLI Tool Usage 部分，替換它
+        if content.contains("## 🚫 CLI Tool Usage") {
+            // 找到該部分並替換
+            if let Some(start) = content.find("## 🚫 CLI Tool Usage") {
+                // 找到下一個 ## 標題或文件結尾
+                let after_section = &content[start..];
+                if let Some(next_section) = after_section[1..].find("\n## ") {
+                    let end = start + next_section + 1;
+                    content.replace_range(start..end, &tools_section);
+                } else {
+                    // 這是最後一個部分
+                    content.replace_range(start.., &tools_section);
+                }
+            }
+        } else {
+            // 如果沒有，添加到最後
+            content.push('\n');
+            content.push_str(&tools_section);
         }
 
-        Ok(())
+        // 添加工具狀態摘要到文件頂部
+        let status = tools_detector.generate_tools_status(installed_tools);
+        content = format!("<!-- {} -->\n\n{}", status, content);
+
+        content
     }
 
-    fn copy_default_commands(&self, claude_dir: &Path) -> Result<()> {
-        let commands = vec!["analyze", "refactor"];
+    /// Add the hooks of `packs` to the project's settings.json, keeping everything else in it
+    pub fn merge_hooks_into_settings(&self, target_path: &Path, packs: &[&str]) -> Result<()> {
+        let path = target_path.join(SETTINGS_FILE);
+        let mut settings = Settings::load(&path)?;
+        self.add_hooks(&mut settings, packs);
+        settings.save(&path)
+    }
 
-        for command in commands {
-            let content = self.registry.get_default_command_template(command)?;
-            let file_path = claude_dir.join("commands").join(format!("{}.md", command));
-            write_file(&file_path, &content)
-                .context(format!("Failed to create command: {}", command))?;
+    /// The project's settings.json with every hook of the plan and the permission rules
+    /// suggested for the detected tooling added
+    fn settings_json(&self, target_path: &Path) -> Result<String> {
+        let mut settings = Settings::load(&target_path.join(SETTINGS_FILE))?;
+        let packs: Vec<&str> = self.plan.packs.iter().map(|pack| pack.name()).collect();
+        self.add_hooks(&mut settings, &packs);
+        for (kind, rule) in SuggestedPermissions::from_commands(&self.context.commands).rules() {
+            settings.add_permission(kind, rule);
         }
+        settings.to_json()
+    }
+
+    fn add_hooks(&self, settings: &mut Settings, packs: &[&str]) {
+        for hook in self.plan.hooks.iter().filter(|hook| packs.contains(&hook.pack.as_str())) {
+            settings.add_hook(&hook.event, hook.matcher.as_deref(), HookCommand::project_script(&hook.command));
+        }
+    }
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::layer::PROJECT_TEMPLATES_DIR;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_only_opted_in_files_are_rendered() {
+        let project = TempDir::new().unwrap();
+        let pack_dir = project.path().join(PROJECT_TEMPLATES_DIR).join("packs/team");
+        fs::create_dir_all(pack_dir.join("hooks")).unwrap();
+        fs::create_dir_all(pack_dir.join("docs")).unwrap();
+        fs::write(
+            pack_dir.join("pack.toml"),
+            r#"
+[pack]
+name = "team"
+version = "1.0.0"
+
+[[files]]
+source = "hooks"
+dest = ".claude/hooks"
+executable = true
+
+[[files]]
+source = "docs"
+dest = ".claude/docs"
+
+[[files]]
+source = "agents/reviewer.md"
+dest = ".claude/agents/reviewer.md"
+render = true
+"#,
+        )
+        .unwrap();
+        let hook = "#!/bin/bash\nfiles=(\"$@\")\necho \"${#files[@]} files\"\n";
+        fs::write(pack_dir.join("hooks/count.sh"), hook).unwrap();
+        fs::write(pack_dir.join("docs/vue.md"), "Use {{ message }} in templates\n").unwrap();
+        fs::write(pack_dir.join("docs/about.md.tera"), "About {{ project_name }}\n").unwrap();
+        fs::create_dir_all(pack_dir.join("agents")).unwrap();
+        fs::write(pack_dir.join("agents/reviewer.md"), "Reviews {{ language }}\n").unwrap();
+
+        let generator = ConfigGenerator::for_pack(project.path(), Language::Rust, "team", &BTreeMap::new()).unwrap();
+        let changes = generator.plan_changes(project.path()).unwrap();
+        let content = |path: &str| {
+            changes
+                .iter()
+                .find(|change| change.path == Path::new(path))
+                .map(|change| change.content.clone())
+                .unwrap_or_else(|| panic!("{} not planned", path))
+        };
+
+        assert_eq!(content(".claude/hooks/count.sh"), hook);
+        assert_eq!(content(".claude/docs/vue.md"), "Use {{ message }} in templates\n");
+        assert_eq!(content(".claude/agents/reviewer.md"), "Reviews Rust\n");
+        let project_name = project.path().file_name().unwrap().to_string_lossy();
+        assert_eq!(content(".claude/docs/about.md"), format!("About {}\n", project_name));
+    }
+
+    #[test]
+    fn test_language_packs_render_for_their_projects() {
+        for (language, marker) in [
+            (Language::Python, "pyproject.toml"),
+            (Language::Go, "go.mod"),
+            (Language::Java, "pom.xml"),
+            (Language::Elixir, "mix.exs"),
+            (Language::Erlang, "rebar.config"),
+        ] {
+            let project = TempDir::new().unwrap();
+            fs::write(project.path().join(marker), "").unwrap();
+
+            let generator = ConfigGenerator::new(project.path(), language, false, &BTreeMap::new()).unwrap();
+            let changes = generator.plan_changes(project.path()).unwrap();
+
+            let test_all = changes
+                .iter()
+                .find(|change| change.path == Path::new(".claude/commands/dev/test-all.md"))
+                .unwrap();
+            let command = test_all.content.lines().find(|line| line.starts_with('!')).unwrap();
+            assert!(command.len() > 1, "{:?} has no test command", language);
+
+            for change in changes.iter().filter(|change| change.path.extension().is_some_and(|ext| ext == "md")) {
+                assert!(
+                    !change.content.contains("{{") && !change.content.contains("{%"),
+                    "{} was not rendered for {:?}",
+                    change.path.display(),
+                    language
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,393 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Language {
+    TypeScript,
+    JavaScript,
+    Python,
+    Go,
+    Rust,
+    Java,
+    Elixir,
+    Erlang,
+    Kotlin,
+    Scala,
+    CSharp,
+    Cpp,
+    Ruby,
+    Php,
+    Swift,
+    Dart,
+    Zig,
+}
+
+/// How to recognize a language and how its projects are usually built
+pub struct LanguageInfo {
+    pub language: Language,
+    /// Display name
+    pub name: &'static str,
+    /// Identifier used for pack names and code fences
+    pub id: &'static str,
+    /// Other names accepted by `--lang`
+    pub aliases: &'static [&'static str],
+    pub extensions: &'static [&'static str],
+    /// Files at the project root that mark a project; `*.ext` matches any file with that extension
+    pub markers: &'static [&'static str],
+    /// Marker files and the package manager each implies, first match wins
+    pub package_managers: &'static [(&'static str, &'static str)],
+    /// Commands a project usually uses. Languages whose commands depend on the project's
+    /// tooling leave them out and get them from `ProjectDetector`.
+    pub commands: DefaultCommands,
+}
+
+/// Static counterpart of `ProjectCommands`
+pub struct DefaultCommands {
+    pub build: Option<&'static str>,
+    pub test: Option<&'static str>,
+    pub lint: Option<&'static str>,
+    pub format: Option<&'static str>,
+    pub typecheck: Option<&'static str>,
+    pub run: Option<&'static str>,
+}
+
+impl DefaultCommands {
+    const NONE: DefaultCommands = DefaultCommands {
+        build: None,
+        test: None,
+        lint: None,
+        format: None,
+        typecheck: None,
+        run: None,
+    };
+}
+
+/// Every supported language. The order decides ties in detection, e.g. a bare `Cargo.toml`
+/// and `package.json`.
+pub const LANGUAGES: &[LanguageInfo] = &[
+    LanguageInfo {
+        language: Language::Rust,
+        name: "Rust",
+        id: "rust",
+        aliases: &["rs"],
+        extensions: &["rs"],
+        markers: &["Cargo.toml", "Cargo.lock"],
+        package_managers: &[("Cargo.toml", "cargo")],
+        commands: DefaultCommands {
+            build: Some("cargo build"),
+            test: Some("cargo test"),
+            lint: Some("cargo clippy -- -D warnings"),
+            format: Some("cargo fmt"),
+            typecheck: Some("cargo check"),
+            run: Some("cargo run"),
+        },
+    },
+    LanguageInfo {
+        language: Language::Go,
+        name: "Go",
+        id: "go",
+        aliases: &["golang"],
+        extensions: &["go"],
+        markers: &["go.mod", "go.sum"],
+        package_managers: &[("go.mod", "go")],
+        commands: DefaultCommands {
+            build: Some("go build ./..."),
+            test: Some("go test ./..."),
+            lint: Some("go vet ./..."),
+            format: Some("gofmt -w ."),
+            typecheck: None,
+            run: Some("go run ."),
+        },
+    },
+    LanguageInfo {
+        language: Language::TypeScript,
+        name: "TypeScript",
+        id: "typescript",
+        aliases: &["ts"],
+        extensions: &["ts", "tsx"],
+        markers: &["tsconfig.json", "package.json"],
+        package_managers: NODE_PACKAGE_MANAGERS,
+        commands: DefaultCommands::NONE,
+    },
+    LanguageInfo {
+        language: Language::JavaScript,
+        name: "JavaScript",
+        id: "javascript",
+        aliases: &["js"],
+        extensions: &["js", "jsx", "mjs", "cjs"],
+        markers: &["package.json"],
+        package_managers: NODE_PACKAGE_MANAGERS,
+        commands: DefaultCommands::NONE,
+    },
+    LanguageInfo {
+        language: Language::Python,
+        name: "Python",
+        id: "python",
+        aliases: &["py"],
+        extensions: &["py"],
+        markers: &["pyproject.toml", "setup.py", "requirements.txt"],
+        package_managers: &[
+            ("uv.lock", "uv"),
+            ("poetry.lock", "poetry"),
+            ("requirements.txt", "pip"),
+            ("pyproject.toml", "pip"),
+        ],
+        commands: DefaultCommands::NONE,
+    },
+    LanguageInfo {
+        language: Language::Java,
+        name: "Java",
+        id: "java",
+        aliases: &[],
+        extensions: &["java"],
+        // build.gradle.kts is Kotlin's marker; Java projects using it are told apart by their sources
+        markers: &["pom.xml", "build.gradle"],
+        package_managers: &[
+            ("pom.xml", "maven"),
+            ("build.gradle", "gradle"),
+            ("build.gradle.kts", "gradle"),
+        ],
+        commands: DefaultCommands::NONE,
+    },
+    LanguageInfo {
+        language: Language::Elixir,
+        name: "Elixir",
+        id: "elixir",
+        aliases: &["ex"],
+        extensions: &["ex", "exs"],
+        markers: &["mix.exs"],
+        package_managers: &[("mix.exs", "mix")],
+        commands: DefaultCommands {
+            build: Some("mix compile"),
+            test: Some("mix test"),
+            lint: Some("mix credo"),
+            format: Some("mix format"),
+            typecheck: Some("mix dialyzer"),
+            run: Some("iex -S mix"),
+        },
+    },
+    LanguageInfo {
+        language: Language::Erlang,
+        name: "Erlang",
+        id: "erlang",
+        aliases: &["erl"],
+        extensions: &["erl", "hrl"],
+        markers: &["rebar.config", "rebar.lock"],
+        package_managers: &[("rebar.config", "rebar3")],
+        commands: DefaultCommands {
+            build: Some("rebar3 compile"),
+            test: Some("rebar3 eunit"),
+            lint: Some("rebar3 lint"),
+            format: Some("rebar3 fmt"),
+            typecheck: Some("rebar3 dialyzer"),
+            run: Some("rebar3 shell"),
+        },
+    },
+    LanguageInfo {
+        language: Language::Kotlin,
+        name: "Kotlin",
+        id: "kotlin",
+        aliases: &["kt"],
+        extensions: &["kt", "kts"],
+        markers: &["build.gradle.kts", "settings.gradle.kts"],
+        package_managers: &[
+            ("build.gradle.kts", "gradle"),
+            ("build.gradle", "gradle"),
+            ("pom.xml", "maven"),
+        ],
+        commands: DefaultCommands::NONE,
+    },
+    LanguageInfo {
+        language: Language::Scala,
+        name: "Scala",
+        id: "scala",
+        aliases: &[],
+        extensions: &["scala", "sc"],
+        markers: &["build.sbt", "build.sc"],
+        package_managers: &[("build.sbt", "sbt"), ("build.sc", "mill")],
+        commands: DefaultCommands {
+            build: Some("sbt compile"),
+            test: Some("sbt test"),
+            format: Some("sbt scalafmtAll"),
+            run: Some("sbt run"),
+            ..DefaultCommands::NONE
+        },
+    },
+    LanguageInfo {
+        language: Language::CSharp,
+        name: "C#",
+        id: "csharp",
+        aliases: &["cs", "dotnet"],
+        extensions: &["cs"],
+        markers: &["*.sln", "*.csproj", "global.json"],
+        package_managers: &[("*.sln", "dotnet"), ("*.csproj", "dotnet")],
+        commands: DefaultCommands {
+            build: Some("dotnet build"),
+            test: Some("dotnet test"),
+            lint: Some("dotnet format --verify-no-changes"),
+            format: Some("dotnet format"),
+            typecheck: None,
+            run: Some("dotnet run"),
+        },
+    },
+    LanguageInfo {
+        language: Language::Cpp,
+        name: "C/C++",
+        id: "cpp",
+        aliases: &["c", "c++", "cxx"],
+        extensions: &["c", "h", "cc", "cpp", "cxx", "hpp", "hh", "hxx"],
+        markers: &["CMakeLists.txt", "meson.build", "configure.ac", "conanfile.txt", "conanfile.py", "vcpkg.json"],
+        package_managers: &[("vcpkg.json", "vcpkg"), ("conanfile.txt", "conan"), ("conanfile.py", "conan")],
+        commands: DefaultCommands {
+            build: Some("cmake --build build"),
+            test: Some("ctest --test-dir build"),
+            ..DefaultCommands::NONE
+        },
+    },
+    LanguageInfo {
+        language: Language::Ruby,
+        name: "Ruby",
+        id: "ruby",
+        aliases: &["rb"],
+        extensions: &["rb", "rake"],
+        markers: &["Gemfile", "Gemfile.lock", "*.gemspec"],
+        package_managers: &[("Gemfile", "bundler")],
+        commands: DefaultCommands {
+            test: Some("bundle exec rake test"),
+            lint: Some("bundle exec rubocop"),
+            format: Some("bundle exec rubocop -a"),
+            ..DefaultCommands::NONE
+        },
+    },
+    LanguageInfo {
+        language: Language::Php,
+        name: "PHP",
+        id: "php",
+        aliases: &[],
+        extensions: &["php"],
+        markers: &["composer.json", "composer.lock"],
+        package_managers: &[("composer.json", "composer")],
+        commands: DefaultCommands {
+            test: Some("vendor/bin/phpunit"),
+            lint: Some("vendor/bin/phpstan analyse"),
+            format: Some("vendor/bin/php-cs-fixer fix"),
+            ..DefaultCommands::NONE
+        },
+    },
+    LanguageInfo {
+        language: Language::Swift,
+        name: "Swift",
+        id: "swift",
+        aliases: &[],
+        extensions: &["swift"],
+        markers: &["Package.swift", "*.xcodeproj"],
+        package_managers: &[("Package.swift", "swiftpm"), ("Podfile", "cocoapods")],
+        commands: DefaultCommands {
+            build: Some("swift build"),
+            test: Some("swift test"),
+            lint: Some("swiftlint"),
+            format: Some("swift format --in-place --recursive ."),
+            typecheck: None,
+            run: Some("swift run"),
+        },
+    },
+    LanguageInfo {
+        language: Language::Dart,
+        name: "Dart",
+        id: "dart",
+        aliases: &["flutter"],
+        extensions: &["dart"],
+        markers: &["pubspec.yaml"],
+        package_managers: &[("pubspec.yaml", "dart")],
+        commands: DefaultCommands {
+            test: Some("dart test"),
+            lint: Some("dart analyze"),
+            format: Some("dart format ."),
+            run: Some("dart run"),
+            ..DefaultCommands::NONE
+        },
+    },
+    LanguageInfo {
+        language: Language::Zig,
+        name: "Zig",
+        id: "zig",
+        aliases: &[],
+        extensions: &["zig"],
+        markers: &["build.zig", "build.zig.zon"],
+        package_managers: &[("build.zig", "zig")],
+        commands: DefaultCommands {
+            build: Some("zig build"),
+            test: Some("zig build test"),
+            format: Some("zig fmt ."),
+            run: Some("zig build run"),
+            ..DefaultCommands::NONE
+        },
+    },
+];
+
+const NODE_PACKAGE_MANAGERS: &[(&str, &str)] = &[
+    ("pnpm-lock.yaml", "pnpm"),
+    ("yarn.lock", "yarn"),
+    ("bun.lockb", "bun"),
+    ("bun.lock", "bun"),
+    ("package-lock.json", "npm"),
+    ("package.json", "npm"),
+];
+
+impl Language {
+    /// Every language, in detection priority order
+    pub fn all() -> impl Iterator<Item = Language> {
+        LANGUAGES.iter().map(|info| info.language)
+    }
+
+    pub fn info(self) -> &'static LanguageInfo {
+        LANGUAGES
+            .iter()
+            .find(|info| info.language == self)
+            .expect("every language has an entry in LANGUAGES")
+    }
+
+    /// Parse a display name, id or alias, ignoring case
+    pub fn from_str(s: &str) -> Result<Self> {
+        let s = s.to_lowercase();
+        LANGUAGES
+            .iter()
+            .find(|info| info.name.to_lowercase() == s || info.id == s || info.aliases.contains(&s.as_str()))
+            .map(|info| info.language)
+            .ok_or_else(|| anyhow!("Unsupported language: {}", s))
+    }
+
+    pub fn to_string(self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn id(self) -> &'static str {
+        self.info().id
+    }
+
+    pub fn file_extensions(&self) -> &'static [&'static str] {
+        self.info().extensions
+    }
+
+    pub fn marker_files(&self) -> &'static [&'static str] {
+        self.info().markers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_accepts_names_ids_and_aliases() {
+        for language in Language::all() {
+            assert_eq!(Language::from_str(language.to_string()).unwrap(), language);
+            assert_eq!(Language::from_str(language.id()).unwrap(), language);
+        }
+
+        assert_eq!(Language::from_str("C++").unwrap(), Language::Cpp);
+        assert_eq!(Language::from_str("dotnet").unwrap(), Language::CSharp);
+        assert_eq!(Language::from_str("golang").unwrap(), Language::Go);
+        assert!(Language::from_str("cobol").is_err());
+    }
+}
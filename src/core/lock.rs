@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::utils::fs::{read_file, write_file};
+
+/// Lockfile recording what generated each file, relative to the project root
+pub const LOCK_FILE: &str = ".claude/forge.lock";
+
+/// Snapshots of each file as generated, the base for three-way merges, relative to the project root
+pub const BASE_DIR: &str = ".claude/.forge/base";
+
+const LOCK_FORMAT_VERSION: u32 = 1;
+
+/// `sha256:<hex>` of some file content
+pub fn content_hash(content: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// `.claude/forge.lock`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeLock {
+    /// Format version of the lockfile
+    pub version: u32,
+    /// claude-forge version that last wrote the lockfile
+    pub generator: String,
+    /// Generated with `init --minimal`, so only essential files are installed
+    #[serde(default)]
+    pub minimal: bool,
+    #[serde(default)]
+    pub packs: Vec<LockedPack>,
+    /// Template variables the files were rendered with
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    #[serde(default)]
+    pub files: Vec<LockedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPack {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedFile {
+    /// Path relative to the project root
+    pub path: String,
+    /// Path of the template within the template tree
+    pub template: String,
+    pub pack: String,
+    pub pack_version: String,
+    /// Template layer the file was rendered from, e.g. `embedded` or `source:team`
+    pub source: String,
+    /// Commit of the template source, for git sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// Hash of the content as generated
+    pub hash: String,
+}
+
+/// How a file on disk compares with what was generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// Unchanged since it was generated
+    Pristine,
+    /// Edited since it was generated
+    Modified,
+    /// Recorded in the lockfile but deleted
+    Missing,
+    /// Not recorded in the lockfile
+    Untracked,
+}
+
+impl Default for ForgeLock {
+    fn default() -> Self {
+        Self {
+            version: LOCK_FORMAT_VERSION,
+            generator: format!("claude-forge {}", env!("CARGO_PKG_VERSION")),
+            minimal: false,
+            packs: Vec::new(),
+            variables: BTreeMap::new(),
+            files: Vec::new(),
+        }
+    }
+}
+
+impl ForgeLock {
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(LOCK_FILE)
+    }
+
+    /// Load the project's lockfile, or an empty one if it has none
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = Self::path(project_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        toml::from_str(&read_file(&path)?)
+            .context(format!("Invalid {}", LOCK_FILE))
+    }
+
+    pub fn save(&mut self, project_root: &Path) -> Result<()> {
+        self.version = LOCK_FORMAT_VERSION;
+        self.generator = format!("claude-forge {}", env!("CARGO_PKG_VERSION"));
+        self.files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let content = format!(
+            "# Generated by claude-forge. Do not edit.\n\n{}",
+            toml::to_string_pretty(self)?
+        );
+        write_file(&Self::path(project_root), &content)
+    }
+
+    pub fn record_pack(&mut self, pack: LockedPack) {
+        match self.packs.iter_mut().find(|existing| existing.name == pack.name) {
+            Some(existing) => *existing = pack,
+            None => self.packs.push(pack),
+        }
+    }
+
+    /// Add or replace the entry for a file
+    pub fn record_file(&mut self, file: LockedFile) {
+        match self.files.iter_mut().find(|existing| existing.path == file.path) {
+            Some(existing) => *existing = file,
+            None => self.files.push(file),
+        }
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&LockedFile> {
+        let path = lock_path(path);
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    /// Compare the file at `path` (relative to the project root) with the recorded hash
+    pub fn status(&self, project_root: &Path, path: &Path) -> FileStatus {
+        let Some(locked) = self.file(path) else {
+            return FileStatus::Untracked;
+        };
+
+        match std::fs::read_to_string(project_root.join(path)) {
+            Ok(content) if content_hash(&content) == locked.hash => FileStatus::Pristine,
+            Ok(_) => FileStatus::Modified,
+            Err(_) => FileStatus::Missing,
+        }
+    }
+}
+
+/// A relative path as stored in the lockfile, with `/` separators
+pub fn lock_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// The content `path` had when it was last generated
+pub fn read_base(project_root: &Path, path: &Path) -> Option<String> {
+    std::fs::read_to_string(project_root.join(BASE_DIR).join(path)).ok()
+}
+
+pub fn write_base(project_root: &Path, path: &Path, content: &str) -> Result<()> {
+    write_file(&project_root.join(BASE_DIR).join(path), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_status_tells_pristine_from_modified() {
+        let dir = TempDir::new().unwrap();
+        let path = Path::new(".claude/agents/reviewer.md");
+        write_file(&dir.path().join(path), "generated").unwrap();
+
+        let mut lock = ForgeLock::default();
+        lock.record_file(LockedFile {
+            path: lock_path(path),
+            template: "core/agents/reviewer.md".to_string(),
+            pack: "core".to_string(),
+            pack_version: "0.1.0".to_string(),
+            source: "embedded".to_string(),
+            revision: None,
+            hash: content_hash("generated"),
+        });
+        lock.save(dir.path()).unwrap();
+
+        let lock = ForgeLock::load(dir.path()).unwrap();
+        assert_eq!(lock.status(dir.path(), path), FileStatus::Pristine);
+        assert_eq!(lock.status(dir.path(), Path::new(".claude/CLAUDE.md")), FileStatus::Untracked);
+
+        write_file(&dir.path().join(path), "edited").unwrap();
+        assert_eq!(lock.status(dir.path(), path), FileStatus::Modified);
+
+        std::fs::remove_file(dir.path().join(path)).unwrap();
+        assert_eq!(lock.status(dir.path(), path), FileStatus::Missing);
+    }
+}
@@ -0,0 +1,125 @@
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+/// Result of a three-way merge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    pub content: String,
+    /// Number of conflicting regions, each wrapped in conflict markers
+    pub conflicts: usize,
+}
+
+/// Line-based three-way merge of `ours` (local edits) and `theirs` (new template output)
+/// against their common ancestor `base`
+pub fn merge3(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let ours_match = matching_lines(&base, &ours);
+    let theirs_match = matching_lines(&base, &theirs);
+
+    let mut result = MergeResult {
+        content: String::new(),
+        conflicts: 0,
+    };
+    let (mut b, mut o, mut t) = (0, 0, 0);
+
+    loop {
+        // Next base line that both sides kept unchanged
+        let sync = (b..base.len()).find_map(|i| match (ours_match[i], theirs_match[i]) {
+            (Some(oi), Some(ti)) if oi >= o && ti >= t => Some((i, oi, ti)),
+            _ => None,
+        });
+
+        let (bi, oi, ti) = sync.unwrap_or((base.len(), ours.len(), theirs.len()));
+        merge_chunk(&base[b..bi], &ours[o..oi], &theirs[t..ti], &mut result);
+
+        if sync.is_none() {
+            break;
+        }
+
+        result.content.push_str(base[bi]);
+        (b, o, t) = (bi + 1, oi + 1, ti + 1);
+    }
+
+    result
+}
+
+/// For each line of `base`, the index of the same line in `other` if it was kept
+fn matching_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal { old_index, new_index, len } = op {
+            for offset in 0..len {
+                matches[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+
+    matches
+}
+
+fn merge_chunk(base: &[&str], ours: &[&str], theirs: &[&str], result: &mut MergeResult) {
+    let resolved = if ours == theirs || theirs == base {
+        Some(ours)
+    } else if ours == base {
+        Some(theirs)
+    } else {
+        None
+    };
+
+    match resolved {
+        Some(lines) => result.content.extend(lines.iter().copied()),
+        None => {
+            result.conflicts += 1;
+            result.content.push_str("<<<<<<< local\n");
+            push_lines(&mut result.content, ours);
+            result.content.push_str("=======\n");
+            push_lines(&mut result.content, theirs);
+            result.content.push_str(">>>>>>> template\n");
+        }
+    }
+}
+
+fn push_lines(content: &mut String, lines: &[&str]) {
+    for line in lines {
+        content.push_str(line);
+    }
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "# Title\n\nintro\n\n## Commands\nnpm test\n\n## Notes\nnone\n";
+
+    #[test]
+    fn test_merges_edits_to_different_regions() {
+        let ours = "# Title\n\nintro\n\n## Commands\nnpm test\n\n## Notes\nour team uses pnpm\n";
+        let theirs = "# Title\n\nbetter intro\n\n## Commands\nnpm test\nnpm run lint\n\n## Notes\nnone\n";
+
+        let merged = merge3(BASE, ours, theirs);
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(
+            merged.content,
+            "# Title\n\nbetter intro\n\n## Commands\nnpm test\nnpm run lint\n\n## Notes\nour team uses pnpm\n"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_edits_conflict() {
+        let ours = BASE.replace("npm test", "pnpm test");
+        let theirs = BASE.replace("npm test", "npm run test");
+
+        let merged = merge3(BASE, &ours, &theirs);
+        assert_eq!(merged.conflicts, 1);
+        assert!(merged
+            .content
+            .contains("<<<<<<< local\npnpm test\n=======\nnpm run test\n>>>>>>> template\n"));
+        assert!(merged.content.starts_with("# Title\n") && merged.content.ends_with("## Notes\nnone\n"));
+    }
+}
@@ -0,0 +1,248 @@
+use regex::Regex;
+use std::fmt;
+
+use super::detector::ProjectCommands;
+use super::settings::Permissions;
+
+/// Secrets files Claude Code may neither read nor edit.
+/// Mirrors `SENSITIVE_PATTERNS` in `templates/core/hooks/security-check.sh`.
+const SENSITIVE_PATHS: &[&str] = &[
+    "**/.env",
+    "**/.env.*",
+    "**/credentials.json",
+    "**/secrets.*",
+    "**/*.pem",
+    "**/*.key",
+    "**/id_rsa*",
+    "**/*.password*",
+];
+
+/// Read-only git commands allowed in every project
+const GIT_COMMANDS: &[&str] = &["git status", "git diff", "git log"];
+
+/// Rules that allow every shell command, which defeats the point of an allowlist
+const BROAD_RULES: &[&str] = &["Bash", "Bash(*)", "Bash(:*)"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionKind {
+    Allow,
+    Ask,
+    Deny,
+}
+
+impl fmt::Display for PermissionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PermissionKind::Allow => write!(f, "allow"),
+            PermissionKind::Ask => write!(f, "ask"),
+            PermissionKind::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl Permissions {
+    pub fn rules(&self, kind: PermissionKind) -> &[String] {
+        match kind {
+            PermissionKind::Allow => &self.allow,
+            PermissionKind::Ask => &self.ask,
+            PermissionKind::Deny => &self.deny,
+        }
+    }
+
+    pub fn rules_mut(&mut self, kind: PermissionKind) -> &mut Vec<String> {
+        match kind {
+            PermissionKind::Allow => &mut self.allow,
+            PermissionKind::Ask => &mut self.ask,
+            PermissionKind::Deny => &mut self.deny,
+        }
+    }
+}
+
+/// Rules derived from the project's tooling
+pub struct SuggestedPermissions {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl SuggestedPermissions {
+    /// Allow the detected build, test, lint, format and typecheck commands and read-only git,
+    /// and deny access to secrets. Run commands are left out since they start servers.
+    pub fn from_commands(commands: &ProjectCommands) -> Self {
+        let detected = [
+            &commands.build,
+            &commands.test,
+            &commands.lint,
+            &commands.format,
+            &commands.typecheck,
+        ];
+
+        let mut allow = Vec::new();
+        let commands = detected.into_iter().flatten().map(String::as_str).chain(GIT_COMMANDS.iter().copied());
+        for command in commands {
+            let rule = bash_rule(command);
+            if !allow.contains(&rule) {
+                allow.push(rule);
+            }
+        }
+
+        Self {
+            allow,
+            deny: secret_rules(),
+        }
+    }
+
+    /// Each suggested rule with the list it belongs in
+    pub fn rules(&self) -> impl Iterator<Item = (PermissionKind, &str)> {
+        let allow = self.allow.iter().map(|rule| (PermissionKind::Allow, rule.as_str()));
+        let deny = self.deny.iter().map(|rule| (PermissionKind::Deny, rule.as_str()));
+        allow.chain(deny)
+    }
+}
+
+/// A `Bash` prefix rule for a command, e.g. `cargo clippy -- -D warnings` becomes
+/// `Bash(cargo clippy -- -D warnings:*)`. The whole command is kept, so the rule allows
+/// nothing wider than it; the wildcard only allows appending arguments.
+pub fn bash_rule(command: &str) -> String {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    format!("Bash({}:*)", words.join(" "))
+}
+
+/// `Read` and `Edit` deny rules for every sensitive path
+pub fn secret_rules() -> Vec<String> {
+    ["Read", "Edit"]
+        .iter()
+        .flat_map(|tool| SENSITIVE_PATHS.iter().map(move |path| format!("{}({})", tool, path)))
+        .collect()
+}
+
+/// Problems with a set of permission rules
+pub fn audit(permissions: &Permissions) -> Vec<String> {
+    let pattern = Regex::new(r"^(mcp__[\w-]+|[A-Z]\w*)(\(.+\))?$").unwrap();
+    let mut findings = Vec::new();
+
+    for kind in [PermissionKind::Allow, PermissionKind::Ask, PermissionKind::Deny] {
+        for rule in permissions.rules(kind) {
+            if !pattern.is_match(rule) {
+                findings.push(format!("Malformed {} rule: {}", kind, rule));
+            }
+        }
+    }
+
+    for rule in &permissions.allow {
+        if BROAD_RULES.contains(&rule.as_str()) {
+            findings.push(format!("{} allows every shell command without asking", rule));
+        }
+        if permissions.deny.contains(rule) {
+            findings.push(format!("{} is both allowed and denied (deny wins)", rule));
+        }
+    }
+
+    let missing: Vec<String> = secret_rules()
+        .into_iter()
+        .filter(|rule| !permissions.deny.contains(rule))
+        .collect();
+    if !missing.is_empty() {
+        findings.push(format!("Secrets are not denied: {}", missing.join(", ")));
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::settings::Settings;
+
+    fn settings_with_suggested_rules() -> Settings {
+        let mut settings = Settings::default();
+        for (kind, rule) in SuggestedPermissions::from_commands(&ProjectCommands::default()).rules() {
+            settings.add_permission(kind, rule);
+        }
+        settings
+    }
+
+    fn findings(settings: &Settings) -> Vec<String> {
+        audit(settings.permissions.as_ref().unwrap())
+    }
+
+    #[test]
+    fn test_suggested_rules_allow_detected_commands_and_git() {
+        let commands = ProjectCommands {
+            build: Some("cargo build".to_string()),
+            test: Some("go test ./...".to_string()),
+            lint: Some("cargo clippy -- -D warnings".to_string()),
+            run: Some("cargo run".to_string()),
+            ..Default::default()
+        };
+        let suggested = SuggestedPermissions::from_commands(&commands);
+        assert_eq!(
+            suggested.allow,
+            vec![
+                "Bash(cargo build:*)",
+                "Bash(go test ./...:*)",
+                "Bash(cargo clippy -- -D warnings:*)",
+                "Bash(git status:*)",
+                "Bash(git diff:*)",
+                "Bash(git log:*)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggested_rules_deny_secrets() {
+        let suggested = SuggestedPermissions::from_commands(&ProjectCommands::default());
+        assert!(suggested.deny.contains(&"Read(**/.env)".to_string()));
+        assert!(suggested.deny.contains(&"Edit(**/*.pem)".to_string()));
+    }
+
+    #[test]
+    fn test_bash_rule_is_no_wider_than_its_command() {
+        assert_eq!(bash_rule("./gradlew test"), "Bash(./gradlew test:*)");
+        assert_eq!(bash_rule("cmake --build build"), "Bash(cmake --build build:*)");
+        assert_eq!(bash_rule("npm run lint -- --fix"), "Bash(npm run lint -- --fix:*)");
+        assert_eq!(bash_rule("npx  tsc --noEmit"), "Bash(npx tsc --noEmit:*)");
+    }
+
+    #[test]
+    fn test_permissions_are_added_and_removed_once() {
+        let mut settings = Settings::default();
+        assert!(settings.add_permission(PermissionKind::Allow, "Bash(go test:*)"));
+        assert!(!settings.add_permission(PermissionKind::Allow, "Bash(go test:*)"));
+        assert!(settings.remove_permission("Bash(go test:*)"));
+        assert!(!settings.remove_permission("Bash(go test:*)"));
+    }
+
+    #[test]
+    fn test_audit_passes_suggested_rules() {
+        assert!(findings(&settings_with_suggested_rules()).is_empty());
+    }
+
+    #[test]
+    fn test_audit_reports_malformed_rules() {
+        let mut settings = settings_with_suggested_rules();
+        settings.add_permission(PermissionKind::Ask, "read(src)");
+        assert_eq!(findings(&settings), vec!["Malformed ask rule: read(src)"]);
+    }
+
+    #[test]
+    fn test_audit_reports_rules_allowing_every_command() {
+        let mut settings = settings_with_suggested_rules();
+        settings.add_permission(PermissionKind::Allow, "Bash(*)");
+        assert_eq!(findings(&settings), vec!["Bash(*) allows every shell command without asking"]);
+    }
+
+    #[test]
+    fn test_audit_reports_rules_both_allowed_and_denied() {
+        let mut settings = settings_with_suggested_rules();
+        settings.add_permission(PermissionKind::Allow, "Bash(make deploy:*)");
+        settings.add_permission(PermissionKind::Deny, "Bash(make deploy:*)");
+        assert_eq!(findings(&settings), vec!["Bash(make deploy:*) is both allowed and denied (deny wins)"]);
+    }
+
+    #[test]
+    fn test_audit_reports_secrets_that_are_not_denied() {
+        let mut settings = settings_with_suggested_rules();
+        settings.remove_permission("Read(**/*.pem)");
+        assert_eq!(findings(&settings), vec!["Secrets are not denied: Read(**/*.pem)"]);
+    }
+}
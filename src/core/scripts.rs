@@ -0,0 +1,191 @@
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+
+use super::detector::{read_json, read_toml};
+
+/// A task the repository defines, e.g. a package.json script or a Makefile target
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectScript {
+    pub name: String,
+    /// Command that runs it, e.g. `make test` or `pnpm lint`
+    pub command: String,
+    /// File the task is defined in
+    pub source: String,
+}
+
+/// Every task defined in the project's package.json, Makefile, justfile, tox.ini, noxfile.py
+/// and cargo config, in that order
+pub fn detect_scripts(root: &Path, package_manager: Option<&str>) -> Vec<ProjectScript> {
+    let mut scripts = package_json_scripts(root, package_manager.unwrap_or("npm"));
+    scripts.extend(makefile_targets(root));
+    scripts.extend(justfile_recipes(root));
+    scripts.extend(tox_environments(root));
+    scripts.extend(nox_sessions(root));
+    scripts.extend(cargo_aliases(root));
+    scripts
+}
+
+/// How `package_manager` runs a package.json script
+pub fn package_script_command(package_manager: &str, name: &str) -> String {
+    match (package_manager, name) {
+        (_, "test") => format!("{} test", package_manager),
+        ("npm", _) | ("bun", _) => format!("{} run {}", package_manager, name),
+        _ => format!("{} {}", package_manager, name),
+    }
+}
+
+fn script(name: &str, command: String, source: &str) -> ProjectScript {
+    ProjectScript {
+        name: name.to_string(),
+        command,
+        source: source.to_string(),
+    }
+}
+
+fn package_json_scripts(root: &Path, package_manager: &str) -> Vec<ProjectScript> {
+    let Some(json) = read_json(&root.join("package.json")) else { return Vec::new() };
+    let Some(scripts) = json.get("scripts").and_then(|scripts| scripts.as_object()) else { return Vec::new() };
+
+    scripts
+        .keys()
+        .map(|name| script(name, package_script_command(package_manager, name), "package.json"))
+        .collect()
+}
+
+fn makefile_targets(root: &Path) -> Vec<ProjectScript> {
+    let Some((file, content)) = read_first(root, &["GNUmakefile", "Makefile", "makefile"]) else { return Vec::new() };
+    // `name:` at the start of a line, but not `name := value`
+    let target = Regex::new(r"^([A-Za-z0-9][\w.-]*)\s*:([^=]|$)").unwrap();
+
+    let mut scripts: Vec<ProjectScript> = Vec::new();
+    for line in content.lines() {
+        let Some(capture) = target.captures(line) else { continue };
+        let name = &capture[1];
+        if !scripts.iter().any(|existing| existing.name == name) {
+            scripts.push(script(name, format!("make {}", name), file));
+        }
+    }
+
+    scripts
+}
+
+fn justfile_recipes(root: &Path) -> Vec<ProjectScript> {
+    let Some((file, content)) = read_first(root, &["justfile", "Justfile", ".justfile"]) else { return Vec::new() };
+    // `name params...:` at the start of a line, but not `name := value`
+    let recipe = Regex::new(r"^@?([A-Za-z_][\w-]*)[^:=]*:([^=]|$)").unwrap();
+    let keywords = ["set", "alias", "export", "import", "mod"];
+
+    content
+        .lines()
+        .filter_map(|line| recipe.captures(line))
+        .map(|capture| capture[1].to_string())
+        .filter(|name| !keywords.contains(&name.as_str()))
+        .map(|name| script(&name, format!("just {}", name), file))
+        .collect()
+}
+
+fn tox_environments(root: &Path) -> Vec<ProjectScript> {
+    let Ok(content) = std::fs::read_to_string(root.join("tox.ini")) else { return Vec::new() };
+    let mut names: Vec<String> = Vec::new();
+    let mut in_tox = false;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            in_tox = section == "tox";
+            if let Some(name) = section.strip_prefix("testenv:") {
+                names.push(name.trim().to_string());
+            }
+        } else if in_tox {
+            if let Some(list) = line.strip_prefix("envlist").and_then(|rest| rest.trim_start().strip_prefix('=')) {
+                names.extend(list.split(',').map(|name| name.trim().to_string()).filter(|name| !name.is_empty()));
+            }
+        }
+    }
+
+    let mut scripts: Vec<ProjectScript> = Vec::new();
+    for name in names {
+        if !scripts.iter().any(|existing| existing.name == name) {
+            scripts.push(script(&name, format!("tox -e {}", name), "tox.ini"));
+        }
+    }
+    scripts
+}
+
+fn nox_sessions(root: &Path) -> Vec<ProjectScript> {
+    let Ok(content) = std::fs::read_to_string(root.join("noxfile.py")) else { return Vec::new() };
+    let session = Regex::new(r#"@(?:nox\.)?session(\([^)]*\))?\s*\n\s*def\s+(\w+)"#).unwrap();
+    let explicit_name = Regex::new(r#"name\s*=\s*["']([^"']+)["']"#).unwrap();
+
+    session
+        .captures_iter(&content)
+        .map(|capture| {
+            let name = capture
+                .get(1)
+                .and_then(|args| explicit_name.captures(args.as_str()))
+                .map(|named| named[1].to_string())
+                .unwrap_or_else(|| capture[2].to_string());
+            script(&name, format!("nox -s {}", name), "noxfile.py")
+        })
+        .collect()
+}
+
+fn cargo_aliases(root: &Path) -> Vec<ProjectScript> {
+    for file in [".cargo/config.toml", ".cargo/config"] {
+        let Some(config) = read_toml(&root.join(file)) else { continue };
+        let Some(aliases) = config.get("alias").and_then(|alias| alias.as_table()) else { continue };
+
+        return aliases
+            .keys()
+            .map(|name| script(name, format!("cargo {}", name), file))
+            .collect();
+    }
+
+    Vec::new()
+}
+
+fn read_first(root: &Path, names: &[&'static str]) -> Option<(&'static str, String)> {
+    names
+        .iter()
+        .find_map(|name| std::fs::read_to_string(root.join(name)).ok().map(|content| (*name, content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_scripts_from_task_runners() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Makefile"),
+            "VERSION := 1.0\n.PHONY: test\n\nbuild: deps\n\tgo build ./...\n\ntest:\n\tgo test ./...\n%.o: %.c\n",
+        )
+        .unwrap();
+        fs::write(root.join("justfile"), "set shell := [\"bash\", \"-c\"]\n\n# lint all\nlint:\n    ruff check .\n\nrelease version:\n    echo {{version}}\n").unwrap();
+        fs::write(root.join("tox.ini"), "[tox]\nenvlist = py311, lint\n\n[testenv:docs]\ncommands = sphinx-build\n").unwrap();
+        fs::write(root.join("noxfile.py"), "import nox\n\n@nox.session\ndef tests(session):\n    pass\n\n@nox.session(name=\"type-check\")\ndef mypy(session):\n    pass\n").unwrap();
+        fs::create_dir_all(root.join(".cargo")).unwrap();
+        fs::write(root.join(".cargo/config.toml"), "[alias]\nxtask = \"run --package xtask --\"\n").unwrap();
+
+        let commands: Vec<String> = detect_scripts(root, None).into_iter().map(|script| script.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "make build",
+                "make test",
+                "just lint",
+                "just release",
+                "tox -e py311",
+                "tox -e lint",
+                "tox -e docs",
+                "nox -s tests",
+                "nox -s type-check",
+                "cargo xtask",
+            ]
+        );
+    }
+}
@@ -0,0 +1,393 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::permissions::PermissionKind;
+use crate::utils::fs::{read_file, write_file};
+
+/// Project settings read by Claude Code, relative to the project root
+pub const SETTINGS_FILE: &str = ".claude/settings.json";
+
+/// Personal project settings, not checked in, relative to the project root
+pub const LOCAL_SETTINGS_FILE: &str = ".claude/settings.local.json";
+
+/// Prefix of hook commands, so hooks run the project's scripts whatever the working directory
+const PROJECT_DIR_PREFIX: &str = "\"$CLAUDE_PROJECT_DIR\"/";
+
+/// A Claude Code `settings.json`. Keys this type does not model are kept as they are.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+
+    /// Hook event (e.g. `PreToolUse`) to the matchers registered for it
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hooks: BTreeMap<String, Vec<HookMatcher>>,
+
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Permissions {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ask: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Hooks run for tools matching `matcher` (all tools if unset)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookMatcher {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+
+    pub hooks: Vec<HookCommand>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookCommand {
+    /// Always `command`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    pub command: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl HookCommand {
+    /// Run a script installed in the project, e.g. `.claude/hooks/security-check.sh`
+    pub fn project_script(path: &str) -> Self {
+        Self {
+            kind: "command".to_string(),
+            command: format!("{}{}", PROJECT_DIR_PREFIX, path),
+            timeout: None,
+        }
+    }
+
+    /// The project-relative script this hook runs, if it runs one
+    pub fn project_script_path(&self) -> Option<&str> {
+        self.command.strip_prefix(PROJECT_DIR_PREFIX)
+    }
+}
+
+impl Settings {
+    /// Load a settings file, or empty settings if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        serde_json::from_str(&read_file(path)?)
+            .context(format!("Invalid settings file: {}", path.display()))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(format!("{}\n", serde_json::to_string_pretty(self)?))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_file(path, &self.to_json()?)
+    }
+
+    /// Register a hook unless the same command is already registered for the event and matcher
+    pub fn add_hook(&mut self, event: &str, matcher: Option<&str>, hook: HookCommand) {
+        let matchers = self.hooks.entry(event.to_string()).or_default();
+
+        match matchers.iter_mut().find(|entry| entry.matcher.as_deref() == matcher) {
+            Some(entry) => {
+                if !entry.hooks.iter().any(|existing| existing.command == hook.command) {
+                    entry.hooks.push(hook);
+                }
+            }
+            None => matchers.push(HookMatcher {
+                matcher: matcher.map(str::to_string),
+                hooks: vec![hook],
+            }),
+        }
+    }
+
+    /// Add a permission rule unless it is already in that list. Returns whether it was added.
+    pub fn add_permission(&mut self, kind: PermissionKind, rule: &str) -> bool {
+        let rules = self.permissions.get_or_insert_with(Permissions::default).rules_mut(kind);
+        if rules.iter().any(|existing| existing == rule) {
+            return false;
+        }
+
+        rules.push(rule.to_string());
+        true
+    }
+
+    /// Remove a permission rule from every list. Returns whether it was found.
+    pub fn remove_permission(&mut self, rule: &str) -> bool {
+        let Some(permissions) = &mut self.permissions else { return false };
+
+        let mut found = false;
+        for kind in [PermissionKind::Allow, PermissionKind::Ask, PermissionKind::Deny] {
+            let rules = permissions.rules_mut(kind);
+            let before = rules.len();
+            rules.retain(|existing| existing != rule);
+            found |= rules.len() != before;
+        }
+
+        found
+    }
+
+    /// Every hook command, with its event
+    pub fn hook_commands(&self) -> impl Iterator<Item = (&str, &HookCommand)> {
+        self.hooks.iter().flat_map(|(event, matchers)| {
+            matchers
+                .iter()
+                .flat_map(move |entry| entry.hooks.iter().map(move |hook| (event.as_str(), hook)))
+        })
+    }
+}
+
+/// A settings file, in increasing order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SettingsLayer {
+    /// `~/.claude/settings.json`
+    User,
+    /// `.claude/settings.json`, checked in
+    Project,
+    /// `.claude/settings.local.json`, not checked in
+    Local,
+}
+
+impl SettingsLayer {
+    pub fn path(self, project_root: &Path) -> Result<PathBuf> {
+        Ok(match self {
+            SettingsLayer::User => dirs::home_dir()
+                .context("Failed to get home directory")?
+                .join(".claude")
+                .join("settings.json"),
+            SettingsLayer::Project => project_root.join(SETTINGS_FILE),
+            SettingsLayer::Local => project_root.join(LOCAL_SETTINGS_FILE),
+        })
+    }
+}
+
+impl fmt::Display for SettingsLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsLayer::User => write!(f, "user"),
+            SettingsLayer::Project => write!(f, "project"),
+            SettingsLayer::Local => write!(f, "local"),
+        }
+    }
+}
+
+/// A value in the effective settings and the layer it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingOrigin {
+    /// Dotted path, e.g. `permissions.allow` or `env.DEBUG`
+    pub key: String,
+    pub value: Value,
+    pub layer: SettingsLayer,
+}
+
+/// The user, project and local settings files merged the way Claude Code merges them:
+/// later layers override values, while permission rules and hooks from every layer apply
+pub struct LayeredSettings {
+    /// Layers that exist, with their path
+    pub layers: Vec<(SettingsLayer, PathBuf)>,
+    merged: Value,
+    origins: Vec<SettingOrigin>,
+}
+
+impl LayeredSettings {
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let mut paths = Vec::new();
+        for layer in [SettingsLayer::User, SettingsLayer::Project, SettingsLayer::Local] {
+            paths.push((layer, layer.path(project_root)?));
+        }
+
+        Self::load_from(paths)
+    }
+
+    /// Merge the given files, which must be in increasing order of precedence; missing files are skipped
+    pub fn load_from(paths: Vec<(SettingsLayer, PathBuf)>) -> Result<Self> {
+        let mut settings = Self {
+            layers: Vec::new(),
+            merged: Value::Object(Map::new()),
+            origins: Vec::new(),
+        };
+
+        for (layer, path) in paths {
+            if !path.exists() {
+                continue;
+            }
+
+            let value: Value = serde_json::from_str(&read_file(&path)?)
+                .context(format!("Invalid settings file: {}", path.display()))?;
+            merge_value(&mut settings.merged, &value, "", layer, &mut settings.origins);
+            settings.layers.push((layer, path));
+        }
+
+        Ok(settings)
+    }
+
+    /// The merged settings
+    pub fn effective(&self) -> Result<Settings> {
+        serde_json::from_value(self.merged.clone())
+            .context("Merged settings do not match the Claude Code settings schema")
+    }
+
+    /// Every value in the effective settings with the layer it came from, sorted by key
+    pub fn origins(&self) -> Vec<SettingOrigin> {
+        let mut origins = self.origins.clone();
+        origins.sort_by(|a, b| a.key.cmp(&b.key));
+        origins
+    }
+}
+
+/// Arrays whose entries from every layer apply, rather than the last layer's array
+fn is_concatenated(key: &str) -> bool {
+    matches!(
+        key,
+        "permissions.allow" | "permissions.ask" | "permissions.deny" | "permissions.additionalDirectories"
+    ) || (key.starts_with("hooks.") && key.matches('.').count() == 1)
+}
+
+fn merge_value(target: &mut Value, incoming: &Value, key: &str, layer: SettingsLayer, origins: &mut Vec<SettingOrigin>) {
+    match (&mut *target, incoming) {
+        (Value::Object(existing), Value::Object(entries)) => {
+            for (name, value) in entries {
+                let child = if key.is_empty() { name.clone() } else { format!("{}.{}", key, name) };
+                let slot = existing.entry(name.clone()).or_insert(Value::Null);
+                merge_value(slot, value, &child, layer, origins);
+            }
+        }
+        (Value::Array(existing), Value::Array(items)) if is_concatenated(key) => {
+            for item in items {
+                if !existing.contains(item) {
+                    existing.push(item.clone());
+                    origins.push(SettingOrigin {
+                        key: key.to_string(),
+                        value: item.clone(),
+                        layer,
+                    });
+                }
+            }
+        }
+        _ => {
+            // Replaced by this layer: forget where the old value and anything under it came from
+            let prefix = format!("{}.", key);
+            origins.retain(|origin| origin.key != key && !origin.key.starts_with(&prefix));
+
+            match incoming {
+                Value::Object(_) => {
+                    *target = Value::Object(Map::new());
+                    merge_value(target, incoming, key, layer, origins);
+                }
+                Value::Array(_) if is_concatenated(key) => {
+                    *target = Value::Array(Vec::new());
+                    merge_value(target, incoming, key, layer, origins);
+                }
+                _ => {
+                    *target = incoming.clone();
+                    origins.push(SettingOrigin {
+                        key: key.to_string(),
+                        value: incoming.clone(),
+                        layer,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip_in_claude_code_schema() {
+        let json = r#"{
+            "model": "opus",
+            "permissions": { "allow": ["Bash(npm test)"], "defaultMode": "acceptEdits" },
+            "hooks": {
+                "PreToolUse": [
+                    { "matcher": "Edit|Write", "hooks": [{ "type": "command", "command": "echo hi" }] }
+                ]
+            }
+        }"#;
+
+        let mut settings: Settings = serde_json::from_str(json).unwrap();
+        settings.add_hook("PreToolUse", Some("Edit|Write"), HookCommand::project_script(".claude/hooks/check.sh"));
+        settings.add_hook("PreToolUse", Some("Edit|Write"), HookCommand::project_script(".claude/hooks/check.sh"));
+        settings.add_hook("SessionStart", None, HookCommand::project_script(".claude/hooks/init.sh"));
+
+        let value: Value = serde_json::from_str(&settings.to_json().unwrap()).unwrap();
+        assert_eq!(value["model"], "opus");
+        assert_eq!(value["permissions"]["defaultMode"], "acceptEdits");
+        assert_eq!(value["hooks"]["PreToolUse"][0]["hooks"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            value["hooks"]["PreToolUse"][0]["hooks"][1]["command"],
+            "\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/check.sh"
+        );
+        assert!(value["hooks"]["SessionStart"][0].get("matcher").is_none());
+        assert_eq!(value["hooks"]["SessionStart"][0]["hooks"][0]["type"], "command");
+    }
+
+    #[test]
+    fn test_layers_merge_with_precedence_and_origins() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, json: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, json).unwrap();
+            path
+        };
+
+        let user = write("user.json", r#"{"model": "sonnet", "env": {"A": "user", "B": "user"}, "permissions": {"allow": ["Read"]}}"#);
+        let project = write("project.json", r#"{"env": {"A": "project"}, "permissions": {"allow": ["Bash(npm test)", "Read"]}}"#);
+        let local = write("local.json", r#"{"model": "opus", "permissions": {"deny": ["Read(.env)"]}}"#);
+
+        let layered = LayeredSettings::load_from(vec![
+            (SettingsLayer::User, user),
+            (SettingsLayer::Project, project),
+            (SettingsLayer::Local, local),
+            (SettingsLayer::Local, dir.path().join("missing.json")),
+        ])
+        .unwrap();
+        assert_eq!(layered.layers.len(), 3);
+
+        let effective = layered.effective().unwrap();
+        assert_eq!(effective.other["model"], "opus");
+        assert_eq!(effective.env["A"], "project");
+        assert_eq!(effective.env["B"], "user");
+        let permissions = effective.permissions.unwrap();
+        assert_eq!(permissions.allow, vec!["Read", "Bash(npm test)"]);
+        assert_eq!(permissions.deny, vec!["Read(.env)"]);
+
+        let origin_of = |key: &str, value: &str| {
+            layered
+                .origins()
+                .into_iter()
+                .find(|origin| origin.key == key && origin.value == value)
+                .map(|origin| origin.layer)
+        };
+        assert_eq!(origin_of("model", "opus"), Some(SettingsLayer::Local));
+        assert_eq!(origin_of("model", "sonnet"), None);
+        assert_eq!(origin_of("env.A", "project"), Some(SettingsLayer::Project));
+        assert_eq!(origin_of("permissions.allow", "Read"), Some(SettingsLayer::User));
+        assert_eq!(origin_of("permissions.allow", "Bash(npm test)"), Some(SettingsLayer::Project));
+    }
+}
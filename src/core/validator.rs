@@ -1,6 +1,22 @@
 use anyhow::Result;
 use std::path::Path;
 
+use super::settings::{Settings, SETTINGS_FILE};
+
+/// Problems found in a project's `.claude/` configuration
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+}
+
+#[derive(Default)]
 pub struct ConfigValidator;
 
 impl ConfigValidator {
@@ -8,43 +24,95 @@ impl ConfigValidator {
         Self
     }
 
-    pub fn validate(&self, claude_dir: &Path) -> Result<Vec<String>> {
-        let mut errors = Vec::new();
+    /// Check the `.claude/` directory of the project at `project_root`
+    pub fn validate(&self, project_root: &Path) -> Result<ValidationReport> {
+        let mut report = ValidationReport::default();
+        let claude_dir = project_root.join(".claude");
 
-        // Check if .claude directory exists
         if !claude_dir.exists() {
-            errors.push(".claude directory not found".to_string());
-            return Ok(errors);
+            report.errors.push(".claude directory not found".to_string());
+            return Ok(report);
         }
 
         // Check CLAUDE.md
-        let claude_md = claude_dir.join("CLAUDE.md");
-        if !claude_md.exists() {
-            errors.push("CLAUDE.md not found".to_string());
+        if !claude_dir.join("CLAUDE.md").exists() {
+            report.errors.push("CLAUDE.md not found".to_string());
         }
 
-        // Check config.json (optional, but validate if exists)
-        let config_json = claude_dir.join("config.json");
-        if config_json.exists() {
-            if let Err(e) = self.validate_config_json(&config_json) {
-                errors.push(format!("config.json validation failed: {}", e));
+        // Check settings.json (optional, but validate if exists)
+        let settings_json = project_root.join(SETTINGS_FILE);
+        if settings_json.exists() {
+            match Settings::load(&settings_json) {
+                Ok(settings) => {
+                    for (event, hook) in settings.hook_commands() {
+                        if let Some(script) = hook.project_script_path() {
+                            if !project_root.join(script).exists() {
+                                report.errors.push(format!("{} hook runs {}, which does not exist", event, script));
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    report.errors.push("settings.json does not match the Claude Code settings schema".to_string());
+                    report.warnings.push(format!("{:#}", e));
+                }
             }
+        } else {
+            report.warnings.push("settings.json not found (optional)".to_string());
+        }
+
+        if claude_dir.join("config.json").exists() {
+            report.warnings.push("config.json is not read by Claude Code; hooks now live in settings.json".to_string());
         }
 
         // Check directory structure
-        for dir in &["agents", "commands", "hooks"] {
-            let dir_path = claude_dir.join(dir);
-            if !dir_path.exists() {
-                errors.push(format!("{} directory not found", dir));
+        for dir in ["agents", "commands", "hooks"] {
+            if !claude_dir.join(dir).exists() {
+                report.warnings.push(format!("{} directory not found", dir));
             }
         }
 
-        Ok(errors)
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn project(settings: &str) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let claude_dir = dir.path().join(".claude");
+        for sub in ["agents", "commands", "hooks"] {
+            fs::create_dir_all(claude_dir.join(sub)).unwrap();
+        }
+        fs::write(claude_dir.join("CLAUDE.md"), "# Project").unwrap();
+        fs::write(dir.path().join(SETTINGS_FILE), settings).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_complete_configuration_is_clean() {
+        let dir = project("{}");
+        let report = ConfigValidator::new().validate(dir.path()).unwrap();
+        assert!(report.is_clean(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_missing_hook_script_is_an_error() {
+        let dir = project(
+            r#"{"hooks": {"PostToolUse": [{"matcher": "Edit", "hooks": [{"type": "command", "command": "\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/format.sh"}]}]}}"#,
+        );
+        let report = ConfigValidator::new().validate(dir.path()).unwrap();
+        assert_eq!(report.errors, vec!["PostToolUse hook runs .claude/hooks/format.sh, which does not exist"]);
     }
 
-    fn validate_config_json(&self, config_path: &Path) -> Result<()> {
-        let content = std::fs::read_to_string(config_path)?;
-        serde_json::from_str::<serde_json::Value>(&content)?;
-        Ok(())
+    #[test]
+    fn test_invalid_settings_are_an_error() {
+        let dir = project(r#"{"hooks": "none"}"#);
+        let report = ConfigValidator::new().validate(dir.path()).unwrap();
+        assert_eq!(report.errors, vec!["settings.json does not match the Claude Code settings schema"]);
     }
 }
@@ -0,0 +1,83 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::detector::{ProjectCommands, ProjectDetector};
+use crate::core::scripts::{detect_scripts, ProjectScript};
+use crate::core::tools_detector::{InstalledTools, ToolsDetector};
+use crate::core::Language;
+
+/// Variables available to every template
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectContext {
+    pub project_name: String,
+    /// Display name, e.g. `TypeScript`
+    pub language: String,
+    /// Lower-case identifier, e.g. `typescript`
+    pub language_id: String,
+    pub framework: Option<String>,
+    pub package_manager: Option<String>,
+    pub commands: ProjectCommands,
+    /// Tasks the repo defines (package.json scripts, Makefile targets, ...)
+    pub scripts: Vec<ProjectScript>,
+    pub tools: InstalledTools,
+    /// Variables declared by template packs, after overrides
+    pub vars: BTreeMap<String, String>,
+}
+
+impl ProjectContext {
+    /// Build the context from what `ProjectDetector` and `ToolsDetector` find in the project
+    pub fn detect(project_root: &Path, language: Language) -> Self {
+        Self::detect_with_tools(project_root, language, Self::detect_tools(project_root))
+    }
+
+    /// The context of a workspace member, sharing the tools already detected for this project
+    pub fn for_package(&self, package_root: &Path, language: Language) -> Self {
+        Self::detect_with_tools(package_root, language, self.tools.clone())
+    }
+
+    fn detect_with_tools(project_root: &Path, language: Language, tools: InstalledTools) -> Self {
+        let detector = ProjectDetector::new();
+        let package_manager = detector.detect_package_manager(project_root, language);
+        let commands = detector.detect_commands(project_root, language, package_manager.as_deref());
+        let scripts = detect_scripts(project_root, package_manager.as_deref());
+
+        Self {
+            project_name: detector.detect_project_name(project_root),
+            language: language.to_string().into(),
+            language_id: language.id().to_string(),
+            framework: detector.detect_framework(project_root, language),
+            package_manager,
+            commands,
+            scripts,
+            tools,
+            vars: BTreeMap::new(),
+        }
+    }
+
+    /// Like [`ProjectContext::detect`], but also detects the language.
+    ///
+    /// Projects whose language cannot be detected get a generic context.
+    pub fn detect_any(project_root: &Path) -> Self {
+        let detector = ProjectDetector::new();
+
+        match detector.detect(project_root) {
+            Ok(language) => Self::detect(project_root, language),
+            Err(_) => Self {
+                project_name: detector.detect_project_name(project_root),
+                language: "Unknown".to_string(),
+                language_id: "generic".to_string(),
+                framework: None,
+                package_manager: None,
+                commands: ProjectCommands::default(),
+                scripts: detect_scripts(project_root, None),
+                tools: Self::detect_tools(project_root),
+                vars: BTreeMap::new(),
+            },
+        }
+    }
+
+    fn detect_tools(project_root: &Path) -> InstalledTools {
+        ToolsDetector::new().detect(project_root).unwrap_or_default()
+    }
+}
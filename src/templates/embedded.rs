@@ -0,0 +1,49 @@
+use include_dir::{include_dir, Dir};
+
+/// The `templates/` tree, compiled into the binary
+static TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+/// Get the contents of an embedded template by its path relative to `templates/`
+pub fn get_embedded_template(path: &str) -> Option<&'static str> {
+    TEMPLATES
+        .get_file(path)
+        .and_then(|f| f.contents_utf8())
+}
+
+/// List every embedded file under `dir`, recursively, as paths relative to `templates/`
+pub fn list_embedded_files(dir: &str) -> Vec<String> {
+    let mut files = Vec::new();
+
+    if let Some(dir) = TEMPLATES.get_dir(dir) {
+        collect_files(dir, &mut files);
+    }
+
+    files.sort();
+    files
+}
+
+fn collect_files(dir: &Dir<'static>, files: &mut Vec<String>) {
+    for file in dir.files() {
+        files.push(file.path().to_string_lossy().replace('\\', "/"));
+    }
+
+    for sub_dir in dir.dirs() {
+        collect_files(sub_dir, files);
+    }
+}
+
+/// List the names of the immediate sub-directories of an embedded directory
+pub fn list_embedded_dirs(dir: &str) -> Vec<String> {
+    let mut dirs: Vec<String> = TEMPLATES
+        .get_dir(dir)
+        .map(|d| {
+            d.dirs()
+                .filter_map(|sub| sub.path().file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    dirs.sort();
+    dirs
+}
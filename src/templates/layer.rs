@@ -0,0 +1,145 @@
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::embedded::{get_embedded_template, list_embedded_dirs, list_embedded_files};
+use super::source::TemplateSources;
+
+/// Directory name for project-local template overrides
+pub const PROJECT_TEMPLATES_DIR: &str = "claude-forge-templates";
+
+/// Directory where `claude-forge update` installs downloaded templates
+pub fn user_templates_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .context("Failed to get config directory")?;
+
+    Ok(config_dir.join("claude-forge").join("templates"))
+}
+
+/// A place templates can be loaded from. Every layer mirrors the layout of `templates/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateLayer {
+    /// Templates downloaded by `claude-forge update`
+    User(PathBuf),
+    /// Overrides checked into the project under `claude-forge-templates/`
+    Project(PathBuf),
+    /// A template source registered with `templates source add`, at the commit checked out for git sources
+    Source { name: String, root: PathBuf, revision: Option<String> },
+    /// Templates compiled into the binary
+    Embedded,
+}
+
+impl TemplateLayer {
+    /// The default search path: user templates, project-local overrides, registered sources
+    /// (most recently added first), then embedded
+    pub fn default_layers(project_root: &Path) -> Vec<TemplateLayer> {
+        let mut layers = Vec::new();
+
+        if let Ok(dir) = user_templates_dir() {
+            layers.push(TemplateLayer::User(dir));
+        }
+        layers.push(TemplateLayer::Project(project_root.join(PROJECT_TEMPLATES_DIR)));
+        if let Ok(sources) = TemplateSources::load() {
+            for source in sources.sources() {
+                layers.push(TemplateLayer::Source {
+                    name: source.name.clone(),
+                    root: sources.root(source),
+                    revision: source.commit.clone(),
+                });
+            }
+        }
+        layers.push(TemplateLayer::Embedded);
+
+        layers
+    }
+
+    /// Short identifier recorded in the project lockfile
+    pub fn id(&self) -> String {
+        match self {
+            TemplateLayer::User(_) => "user".to_string(),
+            TemplateLayer::Project(_) => "project".to_string(),
+            TemplateLayer::Source { name, .. } => format!("source:{}", name),
+            TemplateLayer::Embedded => "embedded".to_string(),
+        }
+    }
+
+    pub fn revision(&self) -> Option<&str> {
+        match self {
+            TemplateLayer::Source { revision, .. } => revision.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn read(&self, path: &str) -> Option<String> {
+        match self {
+            TemplateLayer::Embedded => get_embedded_template(path).map(|c| c.to_string()),
+            TemplateLayer::User(root) | TemplateLayer::Project(root) | TemplateLayer::Source { root, .. } => {
+                std::fs::read_to_string(root.join(path)).ok()
+            }
+        }
+    }
+
+    /// List every file under `dir`, recursively, as paths relative to the layer root
+    pub fn list_files(&self, dir: &str) -> Vec<String> {
+        match self {
+            TemplateLayer::Embedded => list_embedded_files(dir),
+            TemplateLayer::User(root) | TemplateLayer::Project(root) | TemplateLayer::Source { root, .. } => {
+                let mut files: Vec<String> = WalkDir::new(root.join(dir))
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.file_type().is_file())
+                    .filter_map(|entry| {
+                        entry
+                            .path()
+                            .strip_prefix(root)
+                            .ok()
+                            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+                    })
+                    .collect();
+
+                files.sort();
+                files
+            }
+        }
+    }
+
+    /// List the names of the immediate sub-directories of `dir`
+    pub fn list_dirs(&self, dir: &str) -> Vec<String> {
+        match self {
+            TemplateLayer::Embedded => list_embedded_dirs(dir),
+            TemplateLayer::User(root) | TemplateLayer::Project(root) | TemplateLayer::Source { root, .. } => {
+                let mut dirs: Vec<String> = std::fs::read_dir(root.join(dir))
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .filter(|entry| entry.path().is_dir())
+                            .map(|entry| entry.file_name().to_string_lossy().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                dirs.sort();
+                dirs
+            }
+        }
+    }
+}
+
+impl fmt::Display for TemplateLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateLayer::User(root) => write!(f, "user templates ({})", root.display()),
+            TemplateLayer::Project(root) => write!(f, "project templates ({})", root.display()),
+            TemplateLayer::Source { name, root, .. } => write!(f, "source {} ({})", name, root.display()),
+            TemplateLayer::Embedded => write!(f, "embedded"),
+        }
+    }
+}
+
+/// A template's content together with the layer it was loaded from
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub content: String,
+    pub layer: TemplateLayer,
+}
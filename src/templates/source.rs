@@ -0,0 +1,391 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::utils::fs::{ensure_relative, read_file, write_file};
+
+/// File listing the registered template sources, in the claude-forge config directory
+pub const SOURCES_FILE: &str = "sources.toml";
+
+/// The claude-forge config directory, e.g. `~/.config/claude-forge`
+pub fn forge_config_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .context("Failed to get config directory")?;
+
+    Ok(config_dir.join("claude-forge"))
+}
+
+/// Where a template source lives
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceLocation {
+    /// A git repository, cloned into the cache with the system `git`
+    Git(String),
+    /// A directory used in place
+    Path(PathBuf),
+}
+
+impl SourceLocation {
+    /// Treat URLs, `*.git` paths and anything pinned to a revision as git; other directories as local
+    pub fn parse(location: &str, rev: Option<&str>) -> Result<Self> {
+        let looks_like_git = location.contains("://") || location.starts_with("git@") || location.ends_with(".git");
+
+        if looks_like_git || rev.is_some() {
+            return Ok(SourceLocation::Git(location.to_string()));
+        }
+
+        let path = Path::new(location);
+        if !path.is_dir() {
+            bail!("Template source '{}' is neither a git URL nor an existing directory", location);
+        }
+
+        Ok(SourceLocation::Path(path.canonicalize()?))
+    }
+}
+
+/// A registered template source. Its tree mirrors the layout of `templates/`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateSource {
+    pub name: String,
+
+    #[serde(flatten)]
+    pub location: SourceLocation,
+
+    /// Branch, tag or commit to check out; the remote's default branch if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+
+    /// Commit currently checked out in the cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+
+    /// Sub-directory of the source holding the template tree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+}
+
+impl TemplateSource {
+    /// The template tree has to be inside the source, not reached through `..` or an absolute path
+    fn check_subdir(&self) -> Result<()> {
+        match &self.subdir {
+            Some(subdir) => ensure_relative(subdir)
+                .context(format!("Invalid sub-directory for template source '{}'", self.name)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SourcesFile {
+    #[serde(default)]
+    sources: Vec<TemplateSource>,
+}
+
+/// The registered template sources and their git cache
+pub struct TemplateSources {
+    config_dir: PathBuf,
+    sources: Vec<TemplateSource>,
+}
+
+impl TemplateSources {
+    pub fn load() -> Result<Self> {
+        Self::load_from(&forge_config_dir()?)
+    }
+
+    /// Load `sources.toml` from `config_dir`; sources are cached under `config_dir/sources/`
+    pub fn load_from(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join(SOURCES_FILE);
+        let file: SourcesFile = if path.exists() {
+            toml::from_str(&read_file(&path)?)
+                .context(format!("Invalid {}", path.display()))?
+        } else {
+            SourcesFile::default()
+        };
+        for source in &file.sources {
+            source.check_subdir().context(format!("Invalid {}", path.display()))?;
+        }
+
+        Ok(Self {
+            config_dir: config_dir.to_path_buf(),
+            sources: file.sources,
+        })
+    }
+
+    /// Sources in precedence order, highest first
+    pub fn sources(&self) -> &[TemplateSource] {
+        &self.sources
+    }
+
+    /// Directory holding the source's template tree
+    pub fn root(&self, source: &TemplateSource) -> PathBuf {
+        let checkout = match &source.location {
+            SourceLocation::Git(_) => self.cache_dir(&source.name),
+            SourceLocation::Path(path) => path.clone(),
+        };
+
+        match &source.subdir {
+            Some(subdir) => checkout.join(subdir),
+            None => checkout,
+        }
+    }
+
+    /// Register a source, fetching it if it is a git repository. New sources take precedence over older ones.
+    pub fn add(&mut self, mut source: TemplateSource) -> Result<&TemplateSource> {
+        if source.name.is_empty() || source.name.contains(['/', '\\']) || source.name.starts_with('.') {
+            bail!("Invalid template source name '{}'", source.name);
+        }
+        if self.sources.iter().any(|existing| existing.name == source.name) {
+            bail!("Template source '{}' already exists", source.name);
+        }
+        source.check_subdir()?;
+
+        if let Err(error) = self.fetch_new(&mut source) {
+            // Leave nothing behind that would block adding the source again
+            let cache = self.cache_dir(&source.name);
+            if matches!(source.location, SourceLocation::Git(_)) && cache.exists() {
+                std::fs::remove_dir_all(&cache)?;
+            }
+            return Err(error);
+        }
+
+        self.sources.insert(0, source);
+        self.save()?;
+        Ok(&self.sources[0])
+    }
+
+    /// Clone a new git source and check it out, then make sure its template tree is there
+    fn fetch_new(&self, source: &mut TemplateSource) -> Result<()> {
+        if let SourceLocation::Git(url) = &source.location {
+            let cache = self.cache_dir(&source.name);
+            if cache.exists() {
+                std::fs::remove_dir_all(&cache)?;
+            }
+            std::fs::create_dir_all(cache.parent().unwrap())?;
+
+            git(None, &["clone", "--quiet", "--", url, &cache.to_string_lossy()])
+                .context(format!("Failed to clone {}", url))?;
+            source.commit = Some(checkout(&cache, source.rev.as_deref())?);
+        }
+
+        let root = self.root(source);
+        if !root.is_dir() {
+            bail!("Template source '{}' has no directory {}", source.name, root.display());
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<TemplateSource> {
+        let index = self
+            .sources
+            .iter()
+            .position(|source| source.name == name)
+            .context(format!("Template source not found: {}", name))?;
+        let source = self.sources.remove(index);
+
+        let cache = self.cache_dir(name);
+        if cache.exists() {
+            std::fs::remove_dir_all(&cache)?;
+        }
+
+        self.save()?;
+        Ok(source)
+    }
+
+    /// Fetch a git source and check out its revision again, optionally re-pinning it to `rev`
+    pub fn update(&mut self, name: &str, rev: Option<&str>) -> Result<&TemplateSource> {
+        let index = self
+            .sources
+            .iter()
+            .position(|source| source.name == name)
+            .context(format!("Template source not found: {}", name))?;
+        let cache = self.cache_dir(name);
+        let source = &mut self.sources[index];
+
+        if let SourceLocation::Git(url) = &source.location {
+            if rev.is_some() {
+                source.rev = rev.map(str::to_string);
+            }
+
+            if cache.join(".git").exists() {
+                git(Some(&cache), &["fetch", "--quiet", "--tags", "--force", "origin"])
+                    .context(format!("Failed to fetch {}", url))?;
+            } else {
+                std::fs::create_dir_all(cache.parent().unwrap())?;
+                git(None, &["clone", "--quiet", "--", url, &cache.to_string_lossy()])
+                    .context(format!("Failed to clone {}", url))?;
+            }
+            source.commit = Some(checkout(&cache, source.rev.as_deref())?);
+
+            self.save()?;
+        }
+
+        Ok(&self.sources[index])
+    }
+
+    fn cache_dir(&self, name: &str) -> PathBuf {
+        self.config_dir.join("sources").join(name)
+    }
+
+    fn save(&self) -> Result<()> {
+        let file = SourcesFile {
+            sources: self.sources.clone(),
+        };
+        write_file(&self.config_dir.join(SOURCES_FILE), &toml::to_string_pretty(&file)?)
+    }
+}
+
+/// Check out `rev` (a remote branch, tag or commit; the remote default branch if unset), returning the commit
+fn checkout(repo: &Path, rev: Option<&str>) -> Result<String> {
+    let candidates = match rev {
+        Some(rev) => vec![format!("origin/{}", rev), rev.to_string()],
+        None => vec!["origin/HEAD".to_string()],
+    };
+
+    let commit = candidates
+        .iter()
+        .find_map(|candidate| git(Some(repo), &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", candidate)]).ok())
+        .context(format!("Revision not found: {}", rev.unwrap_or("HEAD")))?;
+
+    git(Some(repo), &["checkout", "--quiet", "--detach", &commit])?;
+    Ok(commit)
+}
+
+fn git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+
+    let output = command
+        .args(args)
+        .output()
+        .context("Failed to run git. Is it installed?")?;
+
+    if !output.status.success() {
+        bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create a bare repository with two commits, tagging the first `v1`
+    fn bare_repo(dir: &Path) -> String {
+        let work = dir.join("work");
+        let bare = dir.join("templates.git");
+        let agent = work.join("core/agents/team-agent.md");
+        fs::create_dir_all(agent.parent().unwrap()).unwrap();
+
+        git(None, &["init", "--quiet", "-b", "main", &work.to_string_lossy()]).unwrap();
+        let commit = |message: &str| {
+            git(Some(&work), &["add", "-A"]).unwrap();
+            git(Some(&work), &["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "--quiet", "-m", message]).unwrap();
+        };
+
+        fs::write(&agent, "v1").unwrap();
+        commit("v1");
+        git(Some(&work), &["tag", "v1"]).unwrap();
+        fs::write(&agent, "v2").unwrap();
+        commit("v2");
+
+        git(None, &["clone", "--quiet", "--bare", &work.to_string_lossy(), &bare.to_string_lossy()]).unwrap();
+        bare.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_git_source_is_cached_and_pinned() {
+        let dir = TempDir::new().unwrap();
+        let url = bare_repo(dir.path());
+        let config_dir = dir.path().join("config");
+
+        let mut sources = TemplateSources::load_from(&config_dir).unwrap();
+        sources
+            .add(TemplateSource {
+                name: "team".to_string(),
+                location: SourceLocation::parse(&url, None).unwrap(),
+                rev: Some("v1".to_string()),
+                commit: None,
+                subdir: None,
+            })
+            .unwrap();
+
+        let agent = config_dir.join("sources/team/core/agents/team-agent.md");
+        assert_eq!(fs::read_to_string(&agent).unwrap(), "v1");
+
+        // The pin survives reloading, and updating to another revision moves the checkout
+        let mut sources = TemplateSources::load_from(&config_dir).unwrap();
+        assert_eq!(sources.sources()[0].rev.as_deref(), Some("v1"));
+        sources.update("team", Some("main")).unwrap();
+        assert_eq!(fs::read_to_string(&agent).unwrap(), "v2");
+
+        sources.remove("team").unwrap();
+        assert!(!agent.exists());
+        assert!(TemplateSources::load_from(&config_dir).unwrap().sources().is_empty());
+    }
+
+    #[test]
+    fn test_failed_add_leaves_no_checkout() {
+        let dir = TempDir::new().unwrap();
+        let url = bare_repo(dir.path());
+        let config_dir = dir.path().join("config");
+        let source = |location: &str, subdir: Option<&str>| TemplateSource {
+            name: "team".to_string(),
+            location: SourceLocation::Git(location.to_string()),
+            rev: None,
+            commit: None,
+            subdir: subdir.map(str::to_string),
+        };
+
+        let mut sources = TemplateSources::load_from(&config_dir).unwrap();
+        assert!(sources.add(source(&url, Some("missing"))).is_err());
+        assert!(!config_dir.join("sources/team").exists());
+
+        // A URL that looks like an option is passed to git as a URL
+        let marker = dir.path().join("injected");
+        let injected = format!("--upload-pack=touch {}", marker.display());
+        assert!(sources.add(source(&injected, None)).is_err());
+        assert!(!marker.exists());
+
+        sources.add(source(&url, None)).unwrap();
+        assert!(config_dir.join("sources/team/core/agents/team-agent.md").exists());
+    }
+
+    #[test]
+    fn test_subdir_outside_the_source_is_refused() {
+        let dir = TempDir::new().unwrap();
+        let config_dir = dir.path().join("config");
+        let templates = dir.path().join("templates");
+        std::fs::create_dir_all(&templates).unwrap();
+
+        let mut sources = TemplateSources::load_from(&config_dir).unwrap();
+        for subdir in ["../../", "/etc"] {
+            let error = sources
+                .add(TemplateSource {
+                    name: "team".to_string(),
+                    location: SourceLocation::Path(templates.clone()),
+                    rev: None,
+                    commit: None,
+                    subdir: Some(subdir.to_string()),
+                })
+                .unwrap_err();
+            assert!(format!("{:#}", error).contains("must be relative"), "{:#}", error);
+        }
+        assert!(sources.sources().is_empty());
+
+        std::fs::create_dir_all(&config_dir).unwrap();
+        std::fs::write(
+            config_dir.join(SOURCES_FILE),
+            format!("[[sources]]\nname = \"team\"\npath = \"{}\"\nsubdir = \"../..\"\n", templates.display()),
+        )
+        .unwrap();
+        let error = TemplateSources::load_from(&config_dir).err().unwrap();
+        assert!(format!("{:#}", error).contains("Path '../..' must be relative"), "{:#}", error);
+    }
+}
@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::version::{Version, VersionReq};
+use crate::templates::source::forge_config_dir;
+use crate::utils::fs::read_file;
+
+/// User catalog in the claude-forge config directory, merged over the embedded one
+pub const CATALOG_FILE: &str = "catalog.toml";
+
+const EMBEDDED_CATALOG: &str = include_str!("catalog.toml");
+
+/// A CLI tool claude-forge can install and recommend
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolSpec {
+    pub name: String,
+    /// Executable looked up on PATH
+    pub binary: String,
+    pub description: String,
+    /// Prints the installed version; `<binary> --version` if unset
+    #[serde(default)]
+    pub version_command: Option<String>,
+    /// Regex whose first capture group is the version in the version command's output;
+    /// the first dotted number if unset
+    #[serde(default)]
+    pub version_pattern: Option<String>,
+    /// Oldest version `tools check` accepts
+    #[serde(default)]
+    pub min_version: Option<Version>,
+    /// Newest version `tools check` accepts; `14` allows any 14.x
+    #[serde(default)]
+    pub max_version: Option<Version>,
+    /// Package per package manager, e.g. `brew = "ripgrep"` or `apt = { name = "fd-find", binary = "fdfind" }`
+    #[serde(default)]
+    pub packages: BTreeMap<String, Package>,
+    /// Prebuilt binaries published for the tool, installed without a package manager
+    #[serde(default)]
+    pub release: Option<ToolRelease>,
+    /// What CLAUDE.md tells Claude about the tool; tools without it are installed but not mentioned
+    #[serde(default)]
+    pub guidance: Option<ToolGuidance>,
+}
+
+/// A tool's package for one package manager
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Package {
+    Name(String),
+    /// A package that installs the tool under another executable name
+    Renamed { name: String, binary: String },
+}
+
+impl Package {
+    pub fn name(&self) -> &str {
+        match self {
+            Package::Name(name) | Package::Renamed { name, .. } => name,
+        }
+    }
+}
+
+/// A release whose archives contain a ready-to-run binary
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolRelease {
+    pub version: String,
+    /// Archive URL; `{version}` and `{target}` are replaced
+    pub url: String,
+    /// Binary inside the archive, with the same placeholders; the first file named like
+    /// the binary if unset
+    #[serde(default)]
+    pub path: Option<String>,
+    /// The download is the executable itself rather than a `.tar.gz` archive
+    #[serde(default)]
+    pub bare_binary: bool,
+    /// Archives by the target triple claude-forge runs on; other platforms are not offered one
+    #[serde(default)]
+    pub targets: BTreeMap<String, ReleaseAsset>,
+}
+
+/// The archive for one platform
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    /// Triple used in the archive name when it differs from the host's, e.g. a musl build
+    #[serde(default)]
+    pub target: Option<String>,
+    /// SHA-256 of the archive, checked before anything is extracted; the archive is not
+    /// offered until it is pinned
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+/// The CLAUDE.md rule for a tool, e.g. "use `rg pattern`, not `grep pattern`"
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolGuidance {
+    pub title: String,
+    /// When the rule applies, shown in parentheses after the title
+    #[serde(default)]
+    pub context: Option<String>,
+    pub usage: String,
+    /// Commands the tool replaces; the first is the fallback when it is missing
+    #[serde(default)]
+    pub replaces: Vec<String>,
+    /// Why to install it, e.g. "faster text search"
+    pub benefit: String,
+}
+
+impl ToolSpec {
+    /// Whether `name` refers to this tool by name or binary
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.binary == name
+    }
+
+    pub fn package(&self, manager: &str) -> Option<&str> {
+        self.packages.get(manager).map(Package::name)
+    }
+
+    /// Executables the tool may be installed as, `binary` first
+    pub fn binaries(&self) -> Vec<&str> {
+        let mut binaries = vec![self.binary.as_str()];
+        for package in self.packages.values() {
+            if let Package::Renamed { binary, .. } = package {
+                if !binaries.contains(&binary.as_str()) {
+                    binaries.push(binary);
+                }
+            }
+        }
+        binaries
+    }
+
+    /// Program and arguments that print the version of the installed `binary`
+    pub fn version_command(&self, binary: &str) -> Vec<String> {
+        match &self.version_command {
+            Some(command) => command.split_whitespace().map(str::to_string).collect(),
+            None => vec![binary.to_string(), "--version".to_string()],
+        }
+    }
+
+    pub fn requirement(&self) -> VersionReq {
+        VersionReq {
+            min: self.min_version.clone(),
+            max: self.max_version.clone(),
+        }
+    }
+
+    /// Shell command suggested to install the tool by hand
+    pub fn install_hint(&self) -> Option<String> {
+        let hints: Vec<String> = ["brew", "cargo", "apt"]
+            .iter()
+            .filter_map(|manager| {
+                let package = self.package(manager)?;
+                Some(match *manager {
+                    "apt" => format!("sudo apt install {}", package),
+                    _ => format!("{} install {}", manager, package),
+                })
+            })
+            .collect();
+
+        match hints.as_slice() {
+            [] => None,
+            [only] => Some(only.clone()),
+            [first, second, ..] => Some(format!("{}  # or: {}", first, second)),
+        }
+    }
+}
+
+/// Every tool claude-forge knows about, in the order they are installed and listed
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolCatalog {
+    #[serde(default)]
+    pub tools: Vec<ToolSpec>,
+}
+
+impl ToolCatalog {
+    /// The catalog shipped with claude-forge
+    pub fn embedded() -> Self {
+        toml::from_str(EMBEDDED_CATALOG).expect("the embedded tool catalog is valid")
+    }
+
+    pub fn load() -> Result<Self> {
+        Self::load_from(&forge_config_dir()?)
+    }
+
+    /// The embedded catalog with `catalog.toml` from `config_dir` merged over it
+    pub fn load_from(config_dir: &Path) -> Result<Self> {
+        let mut catalog = Self::embedded();
+
+        let path = config_dir.join(CATALOG_FILE);
+        if path.exists() {
+            let user: ToolCatalog = toml::from_str(&read_file(&path)?)
+                .context(format!("Invalid {}", path.display()))?;
+            catalog.merge(user);
+        }
+
+        Ok(catalog)
+    }
+
+    /// Replace tools with the same name and append new ones
+    fn merge(&mut self, other: ToolCatalog) {
+        for tool in other.tools {
+            match self.tools.iter_mut().find(|existing| existing.name == tool.name) {
+                Some(existing) => *existing = tool,
+                None => self.tools.push(tool),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const USER_CATALOG: &str = r#"
+[[tools]]
+name = "fd"
+binary = "fdfind"
+description = "Fast file finder (Debian binary name)"
+packages = { apt = { name = "fd-find", binary = "fdfind" } }
+
+[[tools]]
+name = "jq"
+binary = "jq"
+description = "JSON processor"
+version_command = "jq --version"
+min_version = "1.6"
+packages = { brew = "jq", apt = "jq" }
+"#;
+
+    fn load(user_catalog: &str) -> Result<ToolCatalog> {
+        let config_dir = TempDir::new().unwrap();
+        fs::write(config_dir.path().join(CATALOG_FILE), user_catalog).unwrap();
+        ToolCatalog::load_from(config_dir.path())
+    }
+
+    fn names(catalog: &ToolCatalog) -> Vec<&str> {
+        catalog.tools.iter().map(|tool| tool.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_embedded_catalog_lists_the_default_tools() {
+        let embedded = ToolCatalog::embedded();
+        assert_eq!(names(&embedded), vec!["ripgrep", "fd", "bat", "eza", "dust"]);
+        assert_eq!(embedded.tools[0].install_hint().as_deref(), Some("brew install ripgrep  # or: cargo install ripgrep"));
+        assert_eq!(embedded.tools[2].binaries(), vec!["bat", "batcat"]);
+    }
+
+    #[test]
+    fn test_user_catalog_replaces_tools_by_name() {
+        let catalog = load(USER_CATALOG).unwrap();
+        let fd = &catalog.tools[1];
+        assert!(fd.matches("fdfind"));
+        assert_eq!(fd.package("apt"), Some("fd-find"));
+        assert!(fd.guidance.is_none());
+    }
+
+    #[test]
+    fn test_user_catalog_adds_new_tools_last() {
+        let catalog = load(USER_CATALOG).unwrap();
+        assert_eq!(names(&catalog), vec!["ripgrep", "fd", "bat", "eza", "dust", "jq"]);
+        assert_eq!(catalog.tools[5].version_command("jq"), vec!["jq", "--version"]);
+        assert_eq!(catalog.tools[5].requirement().to_string(), ">= 1.6");
+    }
+
+    #[test]
+    fn test_incomplete_tool_is_rejected() {
+        assert!(load("[[tools]]\nname = \"broken\"\n").is_err());
+    }
+}
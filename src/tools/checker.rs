@@ -1,44 +1,128 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::Command;
 
-pub struct ToolsChecker;
+use super::catalog::{ToolCatalog, ToolSpec};
+use super::manifest::ToolsLock;
+use super::prebuilt::forge_bin_dir;
+use super::version::{Version, VersionReq};
+
+pub struct ToolsChecker {
+    /// Prebuilt tools live here even when it is not on PATH
+    bin_dir: Option<PathBuf>,
+}
+
+/// Whether a catalog tool is on PATH
+#[derive(Debug, Clone)]
+pub struct ToolStatus {
+    pub spec: ToolSpec,
+    pub installed: bool,
+}
+
+/// A catalog tool's installed version against the versions it has to be within
+#[derive(Debug, Clone)]
+pub struct VersionStatus {
+    pub spec: ToolSpec,
+    pub installed: bool,
+    /// None when the tool is missing or its version could not be read
+    pub version: Option<Version>,
+    pub required: VersionReq,
+    /// The version `.claude/tools.lock` pins
+    pub locked: Option<Version>,
+}
+
+impl VersionStatus {
+    pub fn is_constrained(&self) -> bool {
+        !self.required.is_any() || self.locked.is_some()
+    }
+
+    /// Unconstrained tools always pass; constrained ones need a readable version in range
+    /// that equals the locked one
+    pub fn satisfied(&self) -> bool {
+        if !self.is_constrained() {
+            return true;
+        }
+
+        self.version.as_ref().is_some_and(|version| {
+            self.required.matches(version) && self.locked.as_ref().is_none_or(|locked| version == locked)
+        })
+    }
+}
 
 impl ToolsChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            bin_dir: forge_bin_dir().ok(),
+        }
     }
 
-    pub fn check_all(&self) -> Result<HashMap<String, bool>> {
-        let tools = vec!["rg", "fd", "bat", "eza", "dust"];
-        let mut results = HashMap::new();
+    /// A checker looking for prebuilt tools in `bin_dir` instead of the default bin directory
+    pub fn with_bin_dir(bin_dir: PathBuf) -> Self {
+        Self { bin_dir: Some(bin_dir) }
+    }
 
-        for tool in tools {
-            results.insert(tool.to_string(), self.is_installed(tool));
-        }
+    /// Every tool in `catalog`, in catalog order
+    pub fn check_all(&self, catalog: &ToolCatalog) -> Vec<ToolStatus> {
+        catalog
+            .tools
+            .iter()
+            .map(|spec| ToolStatus {
+                spec: spec.clone(),
+                installed: self.is_installed(spec),
+            })
+            .collect()
+    }
 
-        Ok(results)
+    pub fn is_installed(&self, tool: &ToolSpec) -> bool {
+        self.locate(tool).is_some()
     }
 
-    pub fn is_installed(&self, tool: &str) -> bool {
-        which::which(tool).is_ok()
+    /// Path of the tool's executable under any of its binary names, e.g. `fdfind` on Debian,
+    /// or in the prebuilt bin directory
+    pub fn locate(&self, tool: &ToolSpec) -> Option<PathBuf> {
+        tool.binaries()
+            .into_iter()
+            .find_map(|binary| which::which(binary).ok())
+            .or_else(|| {
+                let prebuilt = self.bin_dir.as_ref()?.join(&tool.binary);
+                prebuilt.is_file().then_some(prebuilt)
+            })
     }
 
-    pub fn get_version(&self, tool: &str) -> Result<Option<String>> {
-        if !self.is_installed(tool) {
-            return Ok(None);
-        }
+    /// Every tool in `catalog` with its installed version, in catalog order
+    pub fn check_versions(&self, catalog: &ToolCatalog, lock: Option<&ToolsLock>) -> Result<Vec<VersionStatus>> {
+        catalog
+            .tools
+            .iter()
+            .map(|spec| {
+                let installed = self.is_installed(spec);
+                let version = if installed { self.get_version(spec)? } else { None };
+                Ok(VersionStatus {
+                    spec: spec.clone(),
+                    installed,
+                    version,
+                    required: spec.requirement(),
+                    locked: lock.and_then(|lock| lock.get(&spec.name)).cloned(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn get_version(&self, tool: &ToolSpec) -> Result<Option<Version>> {
+        let Some(path) = self.locate(tool) else { return Ok(None) };
 
-        let output = Command::new(tool)
-            .arg("--version")
+        let command = tool.version_command(&path.to_string_lossy());
+        let Some((program, args)) = command.split_first() else { return Ok(None) };
+        let output = Command::new(program)
+            .args(args)
             .output()?;
 
-        if output.status.success() {
-            let version = String::from_utf8_lossy(&output.stdout);
-            let version = version.lines().next().unwrap_or("unknown").to_string();
-            Ok(Some(version))
-        } else {
-            Ok(None)
+        if !output.status.success() {
+            return Ok(None);
         }
+
+        // Some tools print their version on stderr
+        let text = format!("{}\n{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        Version::parse_output(&text, tool.version_pattern.as_deref())
     }
 }
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use indicatif::ProgressBar;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use crate::utils::fs::{ensure_directory, write_file};
+
+/// Where each tool's install log is written, `~/.local/share/claude-forge/logs` on Linux
+pub fn forge_log_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .context("Failed to get data directory")?;
+
+    Ok(data_dir.join("claude-forge").join("logs"))
+}
+
+/// Everything done to install one tool, shown on its progress bar as it happens
+#[derive(Debug, Default)]
+pub struct InstallLog {
+    text: String,
+    progress: Option<ProgressBar>,
+}
+
+impl InstallLog {
+    pub fn with_progress(progress: ProgressBar) -> Self {
+        Self {
+            text: String::new(),
+            progress: Some(progress),
+        }
+    }
+
+    /// Record a step and show it as the progress bar's message
+    pub fn step(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if let Some(progress) = &self.progress {
+            progress.set_message(message.clone());
+        }
+        self.text.push_str(&format!("==> {}\n", message));
+    }
+
+    /// Record a finished command with everything it printed
+    pub fn command(&mut self, command: &str, output: &Output) {
+        self.text.push_str(&format!("$ {}\n", command));
+        self.text.push_str(&String::from_utf8_lossy(&output.stdout));
+        self.text.push_str(&String::from_utf8_lossy(&output.stderr));
+        self.text.push_str(&format!("[{}]\n", output.status));
+    }
+
+    /// Write the log to `<dir>/<tool>.log`, replacing the previous install's
+    pub fn save(&self, dir: &Path, tool: &str) -> Result<PathBuf> {
+        ensure_directory(dir)?;
+        let path = dir.join(format!("{}.log", tool));
+        write_file(&path, &self.text)?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for InstallLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
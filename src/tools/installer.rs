@@ -1,70 +1,308 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use colored::Colorize;
-use std::process::Command;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
+use super::catalog::{ToolCatalog, ToolSpec};
 use super::checker::ToolsChecker;
+use super::install_log::{forge_log_dir, InstallLog};
+use super::manifest::{ToolsLock, LOCK_FILE};
+use super::package_manager::{PackageAction, PackageManager, PackageManagers};
+use super::prebuilt::{forge_bin_dir, PrebuiltInstaller, ReleaseDownload};
+use super::state::{forge_state_file, InstallState, InstalledTool};
+use super::version::Version;
+
+/// How many tools are installed at once unless `--jobs` says otherwise
+pub const DEFAULT_JOBS: usize = 4;
+
+/// How a tool was installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallMethod {
+    /// Release archive unpacked into the claude-forge bin directory
+    Prebuilt,
+    Manager(PackageManager),
+}
+
+/// Recorded in the install state as `prebuilt` or the package manager's name
+impl Serialize for InstallMethod {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            InstallMethod::Prebuilt => serializer.serialize_str("prebuilt"),
+            InstallMethod::Manager(manager) => serializer.collect_str(manager),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InstallMethod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "prebuilt" => Ok(InstallMethod::Prebuilt),
+            manager => manager.parse().map(InstallMethod::Manager).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+impl fmt::Display for InstallMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstallMethod::Prebuilt => write!(f, "prebuilt release"),
+            InstallMethod::Manager(manager) => write!(f, "{}", manager),
+        }
+    }
+}
+
+/// What happened to one tool in an install batch
+#[derive(Debug, Clone)]
+pub enum InstallOutcome {
+    Installed(InstallMethod),
+    AlreadyInstalled,
+    Skipped,
+    /// Dry run: how the tool would be installed
+    Planned(String),
+    Failed(String),
+    /// Installed, then removed again because another tool in the batch failed
+    RolledBack(InstallMethod),
+}
+
+#[derive(Debug, Clone)]
+pub struct InstallReport {
+    /// Catalog name
+    pub name: String,
+    /// Binary, as shown to the user
+    pub tool: String,
+    pub outcome: InstallOutcome,
+    /// Output of everything run for the tool
+    pub log: Option<PathBuf>,
+}
+
+/// Every tool's outcome in an install batch, in catalog order
+#[derive(Debug, Clone, Default)]
+pub struct InstallSummary {
+    pub reports: Vec<InstallReport>,
+}
+
+impl InstallSummary {
+    pub fn failed(&self) -> usize {
+        self.reports
+            .iter()
+            .filter(|report| matches!(report.outcome, InstallOutcome::Failed(_)))
+            .count()
+    }
+
+    fn installed_with(&self, method: InstallMethod) -> bool {
+        self.reports
+            .iter()
+            .any(|report| matches!(report.outcome, InstallOutcome::Installed(installed) if installed == method))
+    }
+
+    pub fn print(&self) {
+        let width = self.reports.iter().map(|report| report.tool.len()).max().unwrap_or(0).max(4);
+
+        println!("\n{}", "📋 Summary:".bright_blue());
+        println!("  {:<width$}  {:<18} {}", "Tool".bold(), "Result".bold(), "Details".bold());
+        for report in &self.reports {
+            let (result, details) = match &report.outcome {
+                InstallOutcome::Installed(method) => (format!("{:<18}", "installed").green(), method.to_string()),
+                InstallOutcome::AlreadyInstalled => (format!("{:<18}", "already installed").green(), String::new()),
+                InstallOutcome::Skipped => (format!("{:<18}", "skipped").dimmed(), "--skip".to_string()),
+                InstallOutcome::Planned(plan) => (format!("{:<18}", "would install").blue(), plan.clone()),
+                InstallOutcome::Failed(reason) => {
+                    let log = report.log.as_ref().map(|log| format!(" (log: {})", log.display())).unwrap_or_default();
+                    (format!("{:<18}", "failed").red(), format!("{}{}", reason, log))
+                }
+                InstallOutcome::RolledBack(method) => (format!("{:<18}", "rolled back").yellow(), format!("removed with {}", method)),
+            };
+            println!("  {:<width$}  {} {}", report.tool.bright_yellow(), result, details);
+        }
+    }
+}
 
 pub struct ToolsInstaller {
     checker: ToolsChecker,
+    catalog: ToolCatalog,
+    managers: Arc<PackageManagers>,
+    prebuilt: Arc<PrebuiltInstaller>,
+    log_dir: PathBuf,
+    state_file: PathBuf,
+    /// The project's `.claude/tools.lock`; locked tools are installed at exactly that version
+    lock: Option<ToolsLock>,
 }
 
 impl ToolsInstaller {
-    pub fn new() -> Self {
-        Self {
-            checker: ToolsChecker::new(),
+    pub fn new(catalog: ToolCatalog) -> Result<Self> {
+        let bin_dir = forge_bin_dir()?;
+        Ok(Self {
+            checker: ToolsChecker::with_bin_dir(bin_dir.clone()),
+            catalog,
+            managers: Arc::new(PackageManagers::detect()),
+            prebuilt: Arc::new(PrebuiltInstaller::new(bin_dir)),
+            log_dir: forge_log_dir()?,
+            state_file: forge_state_file()?,
+            lock: None,
+        })
+    }
+
+    /// Install the versions `lock` pins instead of the newest ones
+    pub fn with_lock(mut self, lock: Option<ToolsLock>) -> Self {
+        self.lock = lock;
+        self
+    }
+
+    fn locked_version(&self, tool: &ToolSpec) -> Option<&Version> {
+        self.lock.as_ref()?.get(&tool.name)
+    }
+
+    /// Installed, and at the locked version if the tool is locked
+    fn is_up_to_date(&self, tool: &ToolSpec) -> bool {
+        if !self.checker.is_installed(tool) {
+            return false;
+        }
+
+        match self.locked_version(tool) {
+            Some(locked) => self.checker.get_version(tool).ok().flatten().as_ref() == Some(locked),
+            None => true,
         }
     }
 
+    pub fn catalog(&self) -> &ToolCatalog {
+        &self.catalog
+    }
+
+    /// Install every missing catalog tool, failing if any of them could not be installed
     pub async fn check_and_install(&self) -> Result<()> {
-        let tools = vec![
-            ("rg", "ripgrep"),
-            ("fd", "fd-find"),
-            ("bat", "bat"),
-            ("eza", "eza"),
-            ("dust", "dust"),
-        ];
-
-        for (binary, package) in tools {
-            if self.checker.is_installed(binary) {
-                println!("✓ {} is already installed", binary.green());
+        let summary = self.install_all_with_options(&[], false, DEFAULT_JOBS).await?;
+        summary.print();
+        self.print_path_guidance(&summary);
+
+        if summary.failed() > 0 {
+            bail!("{} tool(s) failed to install", summary.failed());
+        }
+        Ok(())
+    }
+
+    /// Install the missing tools, up to `jobs` at a time, each with its own progress bar and log
+    pub async fn install_all_with_options(&self, skip: &[String], dry_run: bool, jobs: usize) -> Result<InstallSummary> {
+        let mut reports: Vec<Option<InstallReport>> = vec![None; self.catalog.tools.len()];
+        let mut missing = Vec::new();
+        // Tools the user installed themselves, which a locked version replaces but does not make ours
+        let mut preinstalled = Vec::new();
+
+        for (index, tool) in self.catalog.tools.iter().enumerate() {
+            let outcome = if skip.iter().any(|name| tool.matches(name)) {
+                InstallOutcome::Skipped
+            } else if self.is_up_to_date(tool) {
+                InstallOutcome::AlreadyInstalled
+            } else if dry_run {
+                InstallOutcome::Planned(self.plan(tool))
             } else {
-                println!("⚠ {} not found, installing...", binary.yellow());
-                self.install_tool(package).await?;
-            }
+                if self.checker.is_installed(tool) {
+                    preinstalled.push(index);
+                }
+                missing.push((index, tool.clone()));
+                continue;
+            };
+
+            reports[index] = Some(InstallReport {
+                name: tool.name.clone(),
+                tool: tool.binary.clone(),
+                outcome,
+                log: None,
+            });
         }
 
-        Ok(())
+        let installed = self.install_batch(missing, jobs).await?;
+        if !installed.is_empty() {
+            self.record(&installed, &preinstalled)?;
+        }
+        for (index, report) in installed {
+            reports[index] = Some(report);
+        }
+
+        Ok(InstallSummary {
+            reports: reports.into_iter().flatten().collect(),
+        })
     }
 
-    pub async fn install_all_with_options(&self, skip: &[String], dry_run: bool) -> Result<()> {
-        let tools = vec![
-            ("rg", "ripgrep", "Fast text search"),
-            ("fd", "fd-find", "Fast file finder"),
-            ("bat", "bat", "Cat with syntax highlighting"),
-            ("eza", "eza", "Modern ls replacement"),
-            ("dust", "dust", "Disk usage analyzer"),
-        ];
+    /// Remember how each tool of a batch was installed, so it can be uninstalled the same way.
+    /// Tools that were there before the batch are only recorded if claude-forge installed them.
+    fn record(&self, reports: &[(usize, InstallReport)], preinstalled: &[usize]) -> Result<()> {
+        let mut state = InstallState::load_from(&self.state_file)?;
+        let installed_at = chrono::Local::now().to_rfc3339();
 
-        for (binary, package, description) in tools {
-            if skip.contains(&binary.to_string()) {
-                println!("⊘ Skipping {}", binary.dimmed());
+        for (index, report) in reports {
+            let InstallOutcome::Installed(method) = report.outcome else { continue };
+            let tool = &self.catalog.tools[*index];
+            if preinstalled.contains(index) && !state.tools.contains_key(&tool.name) {
                 continue;
             }
+            let (package, path) = match method {
+                InstallMethod::Prebuilt => (None, Some(self.prebuilt.bin_dir().join(&tool.binary))),
+                InstallMethod::Manager(manager) => (tool.package(manager.catalog_key()).map(str::to_string), None),
+            };
+
+            state.tools.insert(
+                tool.name.clone(),
+                InstalledTool {
+                    method,
+                    package,
+                    path,
+                    installed_at: installed_at.clone(),
+                },
+            );
+        }
 
-            print!("  {} - {}: ", binary.bright_yellow(), description.dimmed());
+        state.save_to(&self.state_file)
+    }
 
-            if self.checker.is_installed(binary) {
-                println!("{}", "already installed".green());
-                continue;
+    /// Remove every tool the batch installed, leaving the system as it was before it
+    pub async fn rollback(&self, summary: &mut InstallSummary) -> Result<()> {
+        let mut state = InstallState::load_from(&self.state_file)?;
+
+        for report in &mut summary.reports {
+            let InstallOutcome::Installed(_) = report.outcome else { continue };
+            let Some(entry) = state.tools.get(&report.name).cloned() else { continue };
+
+            match self.remove_recorded(&entry).await {
+                Ok(method) => {
+                    state.tools.remove(&report.name);
+                    report.outcome = InstallOutcome::RolledBack(method);
+                }
+                Err(e) => println!("{} Could not roll back {}: {:#}", "⚠".yellow(), report.tool, e),
             }
+        }
 
-            if dry_run {
-                println!("{}", "would install".blue());
+        state.save_to(&self.state_file)
+    }
+
+    pub async fn update_all_with_options(&self, skip: &[String]) -> Result<()> {
+        for tool in &self.catalog.tools {
+            if skip.iter().any(|name| tool.matches(name)) {
                 continue;
             }
+            // Only upgrade what is there; `tools install` adds the rest
+            let Some(binary) = self.checker.locate(tool) else { continue };
+
+            print!("  {}: ", tool.binary.bright_yellow());
+            let mut log = InstallLog::default();
+            let upgraded = if self.prebuilt.manages(&binary) {
+                // Reinstalling fetches the release the catalog currently pins
+                self.prebuilt.install(tool, &mut log).await.map(|_| InstallMethod::Prebuilt)
+            } else {
+                self.managers
+                    .manage(tool, Some(&binary), PackageAction::Upgrade, &mut log)
+                    .await
+                    .map(InstallMethod::Manager)
+            };
+            let _ = log.save(&self.log_dir, &tool.name);
 
-            match self.install_tool(package).await {
-                Ok(_) => println!("{}", "installed".green()),
+            match upgraded {
+                Ok(method) => println!("{} with {}", "upgraded".green(), method),
                 Err(e) => println!("{}: {}", "failed".red(), e),
             }
         }
@@ -72,109 +310,547 @@ impl ToolsInstaller {
         Ok(())
     }
 
-    pub async fn update_all_with_options(&self, skip: &[String]) -> Result<()> {
-        let platform = self.detect_platform();
-
-        match platform.as_str() {
-            "macos" => self.update_with_brew(skip).await,
-            "linux" => self.update_with_apt_or_cargo(skip).await,
-            _ => {
-                println!("⚠ Platform not supported for automatic updates");
-                println!("Please update tools manually");
-                Ok(())
+    /// Remove the named tools, or every tool claude-forge installed when none are named,
+    /// the way they were installed. Only tools in the install state are touched.
+    pub async fn uninstall(&self, names: &[String]) -> Result<()> {
+        let mut state = InstallState::load_from(&self.state_file)?;
+        let names: Vec<String> = if names.is_empty() {
+            state.tools.keys().cloned().collect()
+        } else {
+            names.to_vec()
+        };
+
+        if names.is_empty() {
+            println!("No tools were installed by claude-forge.");
+            return Ok(());
+        }
+
+        let mut unknown = Vec::new();
+        let mut failed = 0;
+        for name in &names {
+            let tool = self.catalog.tools.iter().find(|tool| tool.matches(name));
+            let key = tool.map_or(name.as_str(), |tool| tool.name.as_str());
+            print!("  {}: ", tool.map_or(name.as_str(), |tool| tool.binary.as_str()).bright_yellow());
+
+            let removed = if let Some(entry) = state.tools.get(key).cloned() {
+                self.remove_recorded(&entry).await
+            } else if tool.is_some() {
+                // Installed by the user or another tool, which is left to remove it
+                println!("{}", "not installed by claude-forge, left alone".dimmed());
+                continue;
+            } else {
+                println!("{}", "not in the tool catalog".red());
+                unknown.push(name.as_str());
+                continue;
+            };
+
+            match removed {
+                Ok(method) => {
+                    state.tools.remove(key);
+                    println!("{} with {}", "removed".green(), method);
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("{}: {}", "failed".red(), e);
+                }
+            }
+        }
+
+        // Saved before reporting errors, so tools removed above are no longer recorded
+        state.save_to(&self.state_file)?;
+
+        let mut problems = Vec::new();
+        if failed > 0 {
+            problems.push(format!("{} tool(s) failed to uninstall", failed));
+        }
+        if !unknown.is_empty() {
+            problems.push(format!("not in the tool catalog: {}", unknown.join(", ")));
+        }
+        if !problems.is_empty() {
+            bail!("{}", problems.join("; "));
+        }
+        Ok(())
+    }
+
+    /// Undo an install recorded in the state file
+    async fn remove_recorded(&self, entry: &InstalledTool) -> Result<InstallMethod> {
+        match entry.method {
+            InstallMethod::Prebuilt => {
+                let path = entry.path.as_ref().context("The install state has no path for this tool")?;
+                if path.exists() {
+                    self.prebuilt.uninstall(path)?;
+                }
+            }
+            InstallMethod::Manager(manager) => {
+                let package = entry.package.as_deref().context("The install state has no package for this tool")?;
+                self.managers
+                    .uninstall_with(manager, package, &mut InstallLog::default())
+                    .await?;
             }
         }
+
+        Ok(entry.method)
     }
 
-    async fn install_tool(&self, package: &str) -> Result<()> {
-        let platform = self.detect_platform();
+    /// How a dry run would install `tool`
+    fn plan(&self, tool: &ToolSpec) -> String {
+        let locked = self.locked_version(tool);
+        if let Some(download) = self.prebuilt.download_for(tool).filter(|download| releases(download, locked)) {
+            return format!("{} from {}", download.version, download.url);
+        }
 
-        match platform.as_str() {
-            "macos" => self.install_with_brew(package).await,
-            "linux" => self.install_with_apt_or_cargo(package).await,
-            _ => self.install_with_cargo(package).await,
+        let candidate = self
+            .managers
+            .candidates(tool)
+            .find(|(manager, _)| locked.is_none() || manager.can_pin());
+        match (candidate, locked) {
+            (Some((manager, package)), Some(locked)) => format!("{} {} with {}", package, locked, manager),
+            (Some((manager, package)), None) => format!("{} with {}", package, manager),
+            (None, Some(locked)) => format!("nothing available installs the locked {}", locked),
+            (None, None) => "no package manager available".to_string(),
         }
     }
 
-    async fn install_with_brew(&self, package: &str) -> Result<()> {
-        let output = Command::new("brew")
-            .args(&["install", package])
-            .output()
-            .context("Failed to run brew install")?;
+    async fn install_batch(&self, tools: Vec<(usize, ToolSpec)>, jobs: usize) -> Result<Vec<(usize, InstallReport)>> {
+        let progress = MultiProgress::new();
+        let style = ProgressStyle::with_template("{spinner:.green} {prefix:.bold} {wide_msg}")
+            .expect("the progress bar template is valid");
+        let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+        let mut tasks = JoinSet::new();
 
-        if !output.status.success() {
-            anyhow::bail!(
-                "brew install failed: {}",
-                String::from_utf8_lossy(&output.stderr)
+        for (index, tool) in tools {
+            let bar = progress.add(
+                ProgressBar::new_spinner()
+                    .with_style(style.clone())
+                    .with_prefix(tool.binary.clone())
+                    .with_message("waiting"),
             );
+            bar.enable_steady_tick(Duration::from_millis(100));
+
+            let managers = Arc::clone(&self.managers);
+            let prebuilt = Arc::clone(&self.prebuilt);
+            let semaphore = Arc::clone(&semaphore);
+            let log_dir = self.log_dir.clone();
+            let locked = self.locked_version(&tool).cloned();
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let mut log = InstallLog::with_progress(bar.clone());
+                let result = install_tool(&managers, &prebuilt, &tool, locked.as_ref(), &mut log).await;
+                let log_path = log.save(&log_dir, &tool.name).ok();
+
+                let outcome = match result {
+                    Ok(method) => {
+                        bar.finish_with_message(format!("{} with {}", "installed".green(), method));
+                        InstallOutcome::Installed(method)
+                    }
+                    Err(e) => {
+                        bar.finish_with_message("failed".red().to_string());
+                        InstallOutcome::Failed(format!("{:#}", e))
+                    }
+                };
+
+                (index, InstallReport { name: tool.name, tool: tool.binary, outcome, log: log_path })
+            });
         }
 
-        Ok(())
+        let mut reports = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            reports.push(joined.context("An install task panicked")?);
+        }
+        Ok(reports)
     }
 
-    async fn install_with_apt_or_cargo(&self, package: &str) -> Result<()> {
-        // Try apt first
-        if which::which("apt").is_ok() {
-            match Command::new("sudo")
-                .args(&["apt", "install", "-y", package])
-                .output()
-            {
-                Ok(output) if output.status.success() => return Ok(()),
-                _ => {}
-            }
+    /// Tell the user to add the bin directory to PATH if prebuilt tools landed there
+    pub fn print_path_guidance(&self, summary: &InstallSummary) {
+        if !summary.installed_with(InstallMethod::Prebuilt) || self.prebuilt.on_path() {
+            return;
         }
 
-        // Fallback to cargo
-        self.install_with_cargo(package).await
+        let bin_dir = self.prebuilt.bin_dir().display();
+        println!("\n{} {} is not on your PATH. Add it to your shell profile:", "⚠".yellow(), bin_dir);
+        println!("  {}", format!("export PATH=\"{}:$PATH\"", bin_dir).bright_yellow());
     }
+}
 
-    async fn install_with_cargo(&self, package: &str) -> Result<()> {
-        let output = Command::new("cargo")
-            .args(&["install", package])
-            .output()
-            .context("Failed to run cargo install")?;
+/// Whether `download` is the locked version, when there is one
+fn releases(download: &ReleaseDownload, locked: Option<&Version>) -> bool {
+    locked.is_none_or(|locked| download.version.parse::<Version>().is_ok_and(|version| version == *locked))
+}
 
-        if !output.status.success() {
-            anyhow::bail!(
-                "cargo install failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
+/// Prefer the tool's prebuilt release, which needs neither root nor a compiler,
+/// then fall back to the package managers. A locked tool is only installed at its
+/// locked version.
+async fn install_tool(
+    managers: &PackageManagers,
+    prebuilt: &PrebuiltInstaller,
+    tool: &ToolSpec,
+    locked: Option<&Version>,
+    log: &mut InstallLog,
+) -> Result<InstallMethod> {
+    let download = prebuilt.download_for(tool);
+    if let (Some(download), Some(locked)) = (&download, locked) {
+        if !releases(download, Some(locked)) {
+            log.step(format!("prebuilt release {} is not the locked {}", download.version, locked));
         }
+    }
 
-        Ok(())
+    let prebuilt_error = match download.filter(|download| releases(download, locked)) {
+        Some(_) => match prebuilt.install(tool, log).await {
+            Ok(_) => return Ok(InstallMethod::Prebuilt),
+            Err(e) => {
+                log.step(format!("prebuilt install failed: {:#}", e));
+                Some(e)
+            }
+        },
+        None => None,
+    };
+
+    let installed = match (managers.install(tool, locked, log).await, prebuilt_error) {
+        (Ok(manager), _) => Ok(InstallMethod::Manager(manager)),
+        (Err(e), Some(prebuilt_error)) => Err(anyhow!("{:#}; {:#}", prebuilt_error, e)),
+        (Err(e), None) => Err(e),
+    };
+
+    match locked {
+        Some(locked) => installed.with_context(|| {
+            format!(
+                "{} is locked to {} in {}; install that version yourself, or run `claude-forge tools lock` to lock the version you have",
+                tool.name, locked, LOCK_FILE
+            )
+        }),
+        None => installed,
     }
+}
 
-    async fn update_with_brew(&self, skip: &[String]) -> Result<()> {
-        let tools = vec!["ripgrep", "fd", "bat", "eza", "dust"];
+// The package managers these tests run are shell scripts
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
 
-        for tool in tools {
-            if skip.contains(&tool.to_string()) {
-                continue;
+    /// Package managers in `bin` and the installer's logs and state in `data`
+    struct Fixture {
+        bin: TempDir,
+        data: TempDir,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self {
+                bin: TempDir::new().unwrap(),
+                data: TempDir::new().unwrap(),
+            }
+        }
+
+        /// A `program` that logs each call to `calls.log` and how many calls are running at
+        /// once to `running.log`, taking a moment so calls overlap. It fails for the packages
+        /// matching the shell pattern `fails_for`.
+        fn manager(&self, program: &str, fails_for: &str) -> &Self {
+            let bin = self.bin.path().display();
+            let script = format!(
+                "#!/bin/sh\n\
+                 echo \"{program} $*\" >> '{bin}/calls.log'\n\
+                 touch '{bin}/active.'\"$2\"\n\
+                 ls '{bin}' | grep -c '^active\\.' >> '{bin}/running.log'\n\
+                 echo \"compiling $2\"\n\
+                 sleep 0.2\n\
+                 rm '{bin}/active.'\"$2\"\n\
+                 case \"$2\" in {fails_for}) echo \"error: $2 failed\" >&2; exit 1;; esac\n"
+            );
+            let path = self.bin.path().join(program);
+            fs::write(&path, script).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            self
+        }
+
+        fn installer(&self, catalog: &str) -> ToolsInstaller {
+            ToolsInstaller {
+                checker: ToolsChecker::with_bin_dir(self.data.path().join("bin")),
+                catalog: toml::from_str(catalog).unwrap(),
+                managers: Arc::new(PackageManagers::detect_in(Some(self.bin.path().as_os_str().to_owned()))),
+                prebuilt: Arc::new(PrebuiltInstaller::new(self.data.path().join("bin"))),
+                log_dir: self.data.path().join("logs"),
+                state_file: self.data.path().join("state.toml"),
+                lock: None,
             }
+        }
 
-            println!("Updating {}...", tool.bright_yellow());
-            let _ = Command::new("brew")
-                .args(&["upgrade", tool])
-                .output();
+        fn calls(&self) -> Vec<String> {
+            fs::read_to_string(self.bin.path().join("calls.log"))
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect()
         }
 
-        Ok(())
+        /// The most manager calls that ran at the same time
+        fn max_running(&self) -> usize {
+            fs::read_to_string(self.bin.path().join("running.log"))
+                .unwrap_or_default()
+                .lines()
+                .map(|count| count.trim().parse().unwrap())
+                .max()
+                .unwrap_or(0)
+        }
     }
 
-    async fn update_with_apt_or_cargo(&self, _skip: &[String]) -> Result<()> {
-        println!("Update via apt/cargo coming soon...");
-        Ok(())
+    /// A catalog of tools installed with `manager`, named `forge-test-a`, `forge-test-b`, ...
+    fn catalog(manager: &str, count: usize) -> String {
+        (b'a'..)
+            .take(count)
+            .map(|letter| {
+                let name = format!("forge-test-{}", letter as char);
+                format!(
+                    "[[tools]]\nname = \"{name}\"\nbinary = \"{name}\"\ndescription = \"Test tool\"\npackages = {{ {manager} = \"{name}\" }}\n\n"
+                )
+            })
+            .collect()
     }
 
-    fn detect_platform(&self) -> String {
-        if cfg!(target_os = "macos") {
-            "macos".to_string()
-        } else if cfg!(target_os = "linux") {
-            "linux".to_string()
-        } else if cfg!(target_os = "windows") {
-            "windows".to_string()
-        } else {
-            "unknown".to_string()
+    fn outcomes(summary: &InstallSummary) -> Vec<String> {
+        summary
+            .reports
+            .iter()
+            .map(|report| match &report.outcome {
+                InstallOutcome::Installed(method) => format!("{} installed with {}", report.tool, method),
+                InstallOutcome::Failed(_) => format!("{} failed", report.tool),
+                other => format!("{} {:?}", report.tool, other),
+            })
+            .collect()
+    }
+
+    fn record_cargo_installs(installer: &ToolsInstaller, tools: &[&str]) {
+        let mut state = InstallState::default();
+        for tool in tools {
+            state.tools.insert(
+                tool.to_string(),
+                InstalledTool {
+                    method: InstallMethod::Manager(PackageManager::Cargo),
+                    package: Some(tool.to_string()),
+                    path: None,
+                    installed_at: "2026-01-01T00:00:00Z".to_string(),
+                },
+            );
+        }
+        state.save_to(&installer.state_file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_tool_does_not_stop_the_batch() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "forge-test-b");
+        let installer = fixture.installer(&catalog("cargo", 3));
+
+        let summary = installer.install_all_with_options(&[], false, 3).await.unwrap();
+
+        assert_eq!(
+            outcomes(&summary),
+            vec![
+                "forge-test-a installed with cargo",
+                "forge-test-b failed",
+                "forge-test-c installed with cargo",
+            ]
+        );
+        assert!(matches!(&summary.reports[1].outcome, InstallOutcome::Failed(reason) if reason.contains("error: forge-test-b failed")));
+    }
+
+    #[tokio::test]
+    async fn test_summary_counts_each_outcome() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "forge-test-b");
+        let mut catalog = catalog("cargo", 3);
+        catalog.push_str("[[tools]]\nname = \"forge-test-brew\"\nbinary = \"forge-test-brew\"\ndescription = \"Test tool\"\npackages = { brew = \"forge-test-brew\" }\n");
+        let installer = fixture.installer(&catalog);
+
+        let summary = installer
+            .install_all_with_options(&["forge-test-c".to_string()], false, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.failed(), 2);
+        assert!(matches!(summary.reports[2].outcome, InstallOutcome::Skipped));
+        assert!(matches!(&summary.reports[3].outcome, InstallOutcome::Failed(reason) if reason.contains("No package manager")));
+        assert!(summary.installed_with(InstallMethod::Manager(PackageManager::Cargo)));
+        assert!(!summary.installed_with(InstallMethod::Prebuilt));
+    }
+
+    #[tokio::test]
+    async fn test_jobs_limits_how_many_tools_install_at_once() {
+        let serial = Fixture::new();
+        serial.manager("cargo", "none");
+        serial.installer(&catalog("cargo", 3)).install_all_with_options(&[], false, 1).await.unwrap();
+        assert_eq!(serial.calls().len(), 3);
+        assert_eq!(serial.max_running(), 1);
+
+        let parallel = Fixture::new();
+        parallel.manager("cargo", "none");
+        parallel.installer(&catalog("cargo", 3)).install_all_with_options(&[], false, 3).await.unwrap();
+        assert!(parallel.max_running() > 1);
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_managers_run_one_at_a_time() {
+        let fixture = Fixture::new();
+        fixture.manager("brew", "none");
+
+        let summary = fixture
+            .installer(&catalog("brew", 3))
+            .install_all_with_options(&[], false, 3)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.failed(), 0);
+        assert_eq!(fixture.max_running(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_each_tool_gets_its_own_log() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "forge-test-b");
+        let installer = fixture.installer(&catalog("cargo", 2));
+
+        let summary = installer.install_all_with_options(&[], false, 2).await.unwrap();
+
+        let log_a = fs::read_to_string(fixture.data.path().join("logs/forge-test-a.log")).unwrap();
+        assert!(log_a.contains("install forge-test-a\ncompiling forge-test-a"));
+        assert!(!log_a.contains("forge-test-b"));
+        let log_b = summary.reports[1].log.as_ref().unwrap();
+        assert!(fs::read_to_string(log_b).unwrap().contains("error: forge-test-b failed"));
+    }
+
+    #[tokio::test]
+    async fn test_install_records_how_tools_were_installed() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "forge-test-b");
+        let installer = fixture.installer(&catalog("cargo", 2));
+
+        installer.install_all_with_options(&[], false, 2).await.unwrap();
+
+        let state = InstallState::load_from(&installer.state_file).unwrap();
+        let recorded: Vec<&str> = state.tools.keys().map(String::as_str).collect();
+        assert_eq!(recorded, vec!["forge-test-a"]);
+        assert_eq!(state.tools["forge-test-a"].method, InstallMethod::Manager(PackageManager::Cargo));
+        assert_eq!(state.tools["forge-test-a"].package.as_deref(), Some("forge-test-a"));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_removes_what_the_batch_installed() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "forge-test-c");
+        let installer = fixture.installer(&catalog("cargo", 3));
+
+        let mut summary = installer.install_all_with_options(&[], false, 3).await.unwrap();
+        installer.rollback(&mut summary).await.unwrap();
+
+        assert!(matches!(summary.reports[0].outcome, InstallOutcome::RolledBack(InstallMethod::Manager(PackageManager::Cargo))));
+        assert!(matches!(summary.reports[2].outcome, InstallOutcome::Failed(_)));
+        assert!(fixture.calls().contains(&"cargo uninstall forge-test-b".to_string()));
+        assert!(InstallState::load_from(&installer.state_file).unwrap().tools.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_only_plans() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "none");
+        let installer = fixture.installer(&catalog("cargo", 1));
+
+        let summary = installer.install_all_with_options(&[], true, 2).await.unwrap();
+
+        assert!(matches!(&summary.reports[0].outcome, InstallOutcome::Planned(plan) if plan == "forge-test-a with cargo"));
+        assert!(fixture.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_locked_tools_install_their_locked_version() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "none");
+        let mut catalog = catalog("cargo", 1);
+        catalog.push_str("[[tools]]\nname = \"forge-test-brew\"\nbinary = \"forge-test-brew\"\ndescription = \"Test tool\"\npackages = { brew = \"forge-test-brew\" }\n");
+        let lock: ToolsLock = toml::from_str("[tools]\nforge-test-a = \"1.2.0\"\nforge-test-brew = \"2.0\"\n").unwrap();
+        let installer = fixture.installer(&catalog).with_lock(Some(lock));
+
+        let dry_run = installer.install_all_with_options(&[], true, 2).await.unwrap();
+        assert!(matches!(&dry_run.reports[0].outcome, InstallOutcome::Planned(plan) if plan == "forge-test-a 1.2.0 with cargo"));
+
+        let summary = installer.install_all_with_options(&[], false, 2).await.unwrap();
+        assert_eq!(outcomes(&summary)[0], "forge-test-a installed with cargo");
+        assert_eq!(fixture.calls(), vec!["cargo install forge-test-a --version 1.2.0"]);
+        assert!(
+            matches!(&summary.reports[1].outcome, InstallOutcome::Failed(reason) if reason.contains("forge-test-brew is locked to 2.0 in .claude/tools.lock")),
+            "{:?}",
+            summary.reports[1].outcome
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reinstalling_a_locked_version_does_not_claim_the_users_tool() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "none");
+        // The user's own forge-test-a 1.0.0 and forge-test-b 1.0.0, of which claude-forge installed b
+        let data_bin = fixture.data.path().join("bin");
+        fs::create_dir_all(&data_bin).unwrap();
+        for tool in ["forge-test-a", "forge-test-b"] {
+            let path = data_bin.join(tool);
+            fs::write(&path, format!("#!/bin/sh\necho '{} 1.0.0'\n", tool)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
         }
+        let lock: ToolsLock = toml::from_str("[tools]\nforge-test-a = \"1.2.0\"\nforge-test-b = \"1.2.0\"\n").unwrap();
+        let installer = fixture.installer(&catalog("cargo", 2)).with_lock(Some(lock));
+        record_cargo_installs(&installer, &["forge-test-b"]);
+
+        let summary = installer.install_all_with_options(&[], false, 2).await.unwrap();
+
+        assert_eq!(outcomes(&summary), vec!["forge-test-a installed with cargo", "forge-test-b installed with cargo"]);
+        let state = InstallState::load_from(&installer.state_file).unwrap();
+        let recorded: Vec<&str> = state.tools.keys().map(String::as_str).collect();
+        assert_eq!(recorded, vec!["forge-test-b"]);
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_saves_state_despite_unknown_tools() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "none");
+        let installer = fixture.installer(&catalog("cargo", 2));
+        record_cargo_installs(&installer, &["forge-test-a"]);
+
+        let names = ["forge-test-a".to_string(), "nope".to_string()];
+        let error = installer.uninstall(&names).await.unwrap_err();
+
+        assert_eq!(error.to_string(), "not in the tool catalog: nope");
+        assert!(InstallState::load_from(&installer.state_file).unwrap().tools.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_fails_when_a_removal_fails() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "forge-test-b");
+        let installer = fixture.installer(&catalog("cargo", 2));
+        record_cargo_installs(&installer, &["forge-test-a", "forge-test-b"]);
+
+        let error = installer.uninstall(&[]).await.unwrap_err();
+
+        assert_eq!(error.to_string(), "1 tool(s) failed to uninstall");
+        let state = InstallState::load_from(&installer.state_file).unwrap();
+        let recorded: Vec<&str> = state.tools.keys().map(String::as_str).collect();
+        assert_eq!(recorded, vec!["forge-test-b"]);
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_leaves_unrecorded_tools_alone() {
+        let fixture = Fixture::new();
+        fixture.manager("cargo", "none");
+        let installer = fixture.installer(&catalog("cargo", 2));
+        record_cargo_installs(&installer, &["forge-test-a"]);
+        // Found, but not installed by claude-forge
+        fs::create_dir_all(fixture.data.path().join("bin")).unwrap();
+        fs::write(fixture.data.path().join("bin/forge-test-b"), "").unwrap();
+
+        installer.uninstall(&["forge-test-b".to_string()]).await.unwrap();
+
+        assert!(fixture.calls().is_empty());
+        assert!(InstallState::load_from(&installer.state_file).unwrap().tools.contains_key("forge-test-a"));
     }
 }
@@ -0,0 +1,208 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::catalog::ToolCatalog;
+use super::checker::VersionStatus;
+use super::version::{Version, VersionReq};
+use crate::utils::fs::{read_file, write_file};
+
+/// The tools a project expects, checked in with the project
+pub const MANIFEST_FILE: &str = ".claude/tools.toml";
+
+/// The versions the project's tools resolved to
+pub const LOCK_FILE: &str = ".claude/tools.lock";
+
+/// `.claude/tools.toml`: catalog tools by name or binary, each with a version requirement
+/// such as `"*"`, `">= 14"` or `"1.7"`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolsManifest {
+    #[serde(default)]
+    pub tools: BTreeMap<String, VersionReq>,
+}
+
+impl ToolsManifest {
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = project_root.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let manifest = toml::from_str(&read_file(&path)?)
+            .context(format!("Invalid {}", path.display()))?;
+        Ok(Some(manifest))
+    }
+
+    /// The catalog narrowed to the manifest's tools, with the manifest's versions replacing
+    /// the catalog's wherever the manifest sets one
+    pub fn apply(&self, mut catalog: ToolCatalog) -> Result<ToolCatalog> {
+        for name in self.tools.keys() {
+            if !catalog.tools.iter().any(|tool| tool.matches(name)) {
+                bail!("{} lists {}, which is not in the tool catalog", MANIFEST_FILE, name);
+            }
+        }
+
+        catalog.tools.retain(|tool| self.tools.keys().any(|name| tool.matches(name)));
+        for tool in &mut catalog.tools {
+            let required = self
+                .tools
+                .iter()
+                .find(|(name, _)| tool.matches(name))
+                .map(|(_, required)| required.clone())
+                .unwrap_or_default();
+
+            if !required.is_any() {
+                tool.min_version = required.min;
+                tool.max_version = required.max;
+            }
+        }
+
+        Ok(catalog)
+    }
+}
+
+/// The tool catalog as it applies to `project_root`: every tool, or only those its
+/// `.claude/tools.toml` lists
+pub fn project_catalog(project_root: &Path) -> Result<ToolCatalog> {
+    let catalog = ToolCatalog::load()?;
+
+    match ToolsManifest::load(project_root)? {
+        Some(manifest) => manifest.apply(catalog),
+        None => Ok(catalog),
+    }
+}
+
+/// `.claude/tools.lock`: the exact version of each manifest tool, by catalog name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolsLock {
+    #[serde(default)]
+    pub tools: BTreeMap<String, Version>,
+}
+
+impl ToolsLock {
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = project_root.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let lock = toml::from_str(&read_file(&path)?)
+            .context(format!("Invalid {}", path.display()))?;
+        Ok(Some(lock))
+    }
+
+    /// Lock the installed versions; every tool has to be installed and within its requirement
+    pub fn resolve(statuses: &[VersionStatus]) -> Result<Self> {
+        let mut tools = BTreeMap::new();
+        let mut unresolved = Vec::new();
+
+        for status in statuses {
+            match &status.version {
+                Some(version) if status.required.is_any() || status.required.matches(version) => {
+                    tools.insert(status.spec.name.clone(), version.clone());
+                }
+                _ => unresolved.push(status.spec.name.as_str()),
+            }
+        }
+
+        if !unresolved.is_empty() {
+            bail!(
+                "Cannot lock tools that are missing, unversioned or outside their requirement: {}",
+                unresolved.join(", ")
+            );
+        }
+
+        Ok(Self { tools })
+    }
+
+    pub fn get(&self, tool: &str) -> Option<&Version> {
+        self.tools.get(tool)
+    }
+
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let content = format!(
+            "# Generated by `claude-forge tools lock`. Commit it so everyone checks against the same versions.\n\n{}",
+            toml::to_string(self)?
+        );
+        write_file(&project_root.join(LOCK_FILE), &content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::catalog::ToolSpec;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// The embedded catalog narrowed to ripgrep at 14 or later, and fd at any version
+    fn narrowed_catalog() -> ToolCatalog {
+        let manifest: ToolsManifest = toml::from_str("[tools]\nrg = \">= 14\"\nfd = \"*\"\n").unwrap();
+        manifest.apply(ToolCatalog::embedded()).unwrap()
+    }
+
+    fn status(tool: &ToolSpec, version: Option<&str>) -> VersionStatus {
+        VersionStatus {
+            spec: tool.clone(),
+            installed: version.is_some(),
+            version: version.map(|version| version.parse().unwrap()),
+            required: tool.requirement(),
+            locked: None,
+        }
+    }
+
+    #[test]
+    fn test_project_without_manifest_has_none() {
+        let project = TempDir::new().unwrap();
+        assert!(ToolsManifest::load(project.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_manifest_narrows_the_catalog() {
+        let project = TempDir::new().unwrap();
+        fs::create_dir_all(project.path().join(".claude")).unwrap();
+        fs::write(project.path().join(MANIFEST_FILE), "[tools]\nrg = \">= 14\"\nfd = \"*\"\n").unwrap();
+
+        let manifest = ToolsManifest::load(project.path()).unwrap().unwrap();
+        let catalog = manifest.apply(ToolCatalog::embedded()).unwrap();
+        let names: Vec<&str> = catalog.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, vec!["ripgrep", "fd"]);
+        assert_eq!(catalog.tools[0].requirement().to_string(), ">= 14");
+        assert!(catalog.tools[1].requirement().is_any());
+    }
+
+    #[test]
+    fn test_unknown_tool_in_manifest_is_rejected() {
+        let unknown: ToolsManifest = toml::from_str("[tools]\nnope = \"*\"\n").unwrap();
+        assert!(unknown.apply(ToolCatalog::embedded()).is_err());
+    }
+
+    #[test]
+    fn test_lock_refuses_unsatisfied_tools() {
+        let catalog = narrowed_catalog();
+        let statuses = [status(&catalog.tools[0], Some("13.0.0")), status(&catalog.tools[1], Some("9.0.0"))];
+        assert!(ToolsLock::resolve(&statuses).is_err());
+    }
+
+    #[test]
+    fn test_lock_refuses_missing_tools() {
+        let catalog = narrowed_catalog();
+        let statuses = [status(&catalog.tools[0], Some("14.1.1")), status(&catalog.tools[1], None)];
+        assert!(ToolsLock::resolve(&statuses).is_err());
+    }
+
+    #[test]
+    fn test_lock_round_trips() {
+        let project = TempDir::new().unwrap();
+        let catalog = narrowed_catalog();
+        let statuses = [status(&catalog.tools[0], Some("14.1.1")), status(&catalog.tools[1], Some("9.0.0"))];
+
+        let lock = ToolsLock::resolve(&statuses).unwrap();
+        lock.save(project.path()).unwrap();
+
+        let content = fs::read_to_string(project.path().join(LOCK_FILE)).unwrap();
+        assert!(content.contains("ripgrep = \"14.1.1\""));
+        assert_eq!(ToolsLock::load(project.path()).unwrap(), Some(lock));
+    }
+}
@@ -1,2 +1,9 @@
+pub mod catalog;
 pub mod checker;
+pub mod install_log;
 pub mod installer;
+pub mod manifest;
+pub mod package_manager;
+pub mod prebuilt;
+pub mod state;
+pub mod version;
@@ -0,0 +1,443 @@
+use anyhow::{bail, Context, Result};
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tokio::sync::Mutex;
+
+use super::catalog::ToolSpec;
+use super::install_log::InstallLog;
+use super::version::Version;
+
+/// A way of installing tools, in the order they are preferred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Apt,
+    Dnf,
+    Pacman,
+    Zypper,
+    Apk,
+    Nix,
+    Brew,
+    /// Downloads prebuilt binaries of crates; uses the catalog's `cargo` packages
+    CargoBinstall,
+    Cargo,
+}
+
+/// What to do with a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageAction {
+    Install,
+    Upgrade,
+    Uninstall,
+}
+
+impl PackageManager {
+    pub const ALL: [PackageManager; 9] = [
+        PackageManager::Apt,
+        PackageManager::Dnf,
+        PackageManager::Pacman,
+        PackageManager::Zypper,
+        PackageManager::Apk,
+        PackageManager::Nix,
+        PackageManager::Brew,
+        PackageManager::CargoBinstall,
+        PackageManager::Cargo,
+    ];
+
+    /// Key of the manager's package in the tool catalog
+    pub fn catalog_key(self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Pacman => "pacman",
+            PackageManager::Zypper => "zypper",
+            PackageManager::Apk => "apk",
+            PackageManager::Nix => "nix",
+            PackageManager::Brew => "brew",
+            PackageManager::CargoBinstall | PackageManager::Cargo => "cargo",
+        }
+    }
+
+    /// Executable that has to be on PATH for the manager to be used
+    fn program(self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt-get",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Pacman => "pacman",
+            PackageManager::Zypper => "zypper",
+            PackageManager::Apk => "apk",
+            PackageManager::Nix => "nix",
+            PackageManager::Brew => "brew",
+            PackageManager::CargoBinstall => "cargo-binstall",
+            PackageManager::Cargo => "cargo",
+        }
+    }
+
+    /// Managers holding a global lock while they run, so claude-forge runs them one at a time
+    fn is_exclusive(self) -> bool {
+        self.needs_root() || matches!(self, PackageManager::Brew | PackageManager::Nix)
+    }
+
+    /// System package managers install into /usr and need root
+    fn needs_root(self) -> bool {
+        matches!(
+            self,
+            PackageManager::Apt | PackageManager::Dnf | PackageManager::Pacman | PackageManager::Zypper | PackageManager::Apk
+        )
+    }
+
+    /// Arguments passed to the manager's program for `action` on `package`
+    fn args(self, action: PackageAction, package: &str) -> Vec<String> {
+        use PackageAction::*;
+
+        let args: Vec<&str> = match (self, action) {
+            (PackageManager::Apt, Install) => vec!["install", "-y", package],
+            (PackageManager::Apt, Upgrade) => vec!["install", "-y", "--only-upgrade", package],
+            (PackageManager::Apt, Uninstall) => vec!["remove", "-y", package],
+            (PackageManager::Dnf, Install) => vec!["install", "-y", package],
+            (PackageManager::Dnf, Upgrade) => vec!["upgrade", "-y", package],
+            (PackageManager::Dnf, Uninstall) => vec!["remove", "-y", package],
+            (PackageManager::Pacman, Install) => vec!["-S", "--noconfirm", "--needed", package],
+            (PackageManager::Pacman, Upgrade) => vec!["-S", "--noconfirm", package],
+            (PackageManager::Pacman, Uninstall) => vec!["-R", "--noconfirm", package],
+            (PackageManager::Zypper, Install) => vec!["--non-interactive", "install", package],
+            (PackageManager::Zypper, Upgrade) => vec!["--non-interactive", "update", package],
+            (PackageManager::Zypper, Uninstall) => vec!["--non-interactive", "remove", package],
+            (PackageManager::Apk, Install) => vec!["add", package],
+            (PackageManager::Apk, Upgrade) => vec!["upgrade", package],
+            (PackageManager::Apk, Uninstall) => vec!["del", package],
+            (PackageManager::Brew, Install) => vec!["install", package],
+            (PackageManager::Brew, Upgrade) => vec!["upgrade", package],
+            (PackageManager::Brew, Uninstall) => vec!["uninstall", package],
+            (PackageManager::Nix, Install) => return vec!["profile".into(), "install".into(), format!("nixpkgs#{}", package)],
+            (PackageManager::Nix, Upgrade) => vec!["profile", "upgrade", package],
+            (PackageManager::Nix, Uninstall) => vec!["profile", "remove", package],
+            // cargo-binstall installs the newest release when it is newer than the installed one
+            (PackageManager::CargoBinstall, Install | Upgrade) => vec!["--no-confirm", package],
+            (PackageManager::Cargo, Install | Upgrade) => vec!["install", package],
+            // cargo-binstall has no uninstall; its crates are removed like any other
+            (PackageManager::CargoBinstall | PackageManager::Cargo, Uninstall) => vec!["uninstall", package],
+        };
+
+        args.into_iter().map(str::to_string).collect()
+    }
+
+    /// Whether the manager can install a chosen version, as `.claude/tools.lock` needs
+    pub fn can_pin(self) -> bool {
+        matches!(self, PackageManager::CargoBinstall | PackageManager::Cargo)
+    }
+
+    /// Arguments installing exactly `version` of `package`; None for managers that only
+    /// install whatever version their repositories carry
+    fn pinned_install_args(self, package: &str, version: &Version) -> Option<Vec<String>> {
+        match self {
+            PackageManager::CargoBinstall => Some(vec!["--no-confirm".into(), format!("{}@{}", package, version)]),
+            PackageManager::Cargo => Some(vec!["install".into(), package.into(), "--version".into(), version.to_string()]),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageManager::CargoBinstall => write!(f, "cargo-binstall"),
+            other => write!(f, "{}", other.catalog_key()),
+        }
+    }
+}
+
+impl std::str::FromStr for PackageManager {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        PackageManager::ALL
+            .into_iter()
+            .find(|manager| manager.to_string() == s)
+            .with_context(|| format!("Unknown package manager '{}'", s))
+    }
+}
+
+impl fmt::Display for PackageAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackageAction::Install => write!(f, "install"),
+            PackageAction::Upgrade => write!(f, "upgrade"),
+            PackageAction::Uninstall => write!(f, "uninstall"),
+        }
+    }
+}
+
+/// The package managers found on PATH, and how to run them
+pub struct PackageManagers {
+    search_path: Option<OsString>,
+    available: Vec<(PackageManager, PathBuf)>,
+    sudo: Option<PathBuf>,
+    /// Held while an exclusive manager runs, e.g. apt-get waiting on the dpkg lock
+    exclusive: Mutex<()>,
+}
+
+impl PackageManagers {
+    pub fn detect() -> Self {
+        Self::detect_in(std::env::var_os("PATH"))
+    }
+
+    /// Look for package managers, and `sudo`, in `search_path` instead of PATH
+    pub fn detect_in(search_path: Option<OsString>) -> Self {
+        let find = |program: &str| {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            which::which_in(program, search_path.clone(), cwd).ok()
+        };
+
+        let available = PackageManager::ALL
+            .iter()
+            .filter_map(|manager| find(manager.program()).map(|path| (*manager, path)))
+            .collect();
+        let sudo = find("sudo");
+
+        Self {
+            search_path,
+            available,
+            sudo,
+            exclusive: Mutex::new(()),
+        }
+    }
+
+    pub fn available(&self) -> impl Iterator<Item = PackageManager> + '_ {
+        self.available.iter().map(|(manager, _)| *manager)
+    }
+
+    /// Available managers that have a package for `tool`, most preferred first
+    pub fn candidates<'a>(&'a self, tool: &'a ToolSpec) -> impl Iterator<Item = (PackageManager, &'a str)> + 'a {
+        self.available()
+            .filter_map(|manager| tool.package(manager.catalog_key()).map(|package| (manager, package)))
+    }
+
+    /// Install `tool` with the first candidate manager that succeeds. With a `version`, only
+    /// managers that can install that exact version are tried.
+    pub async fn install(&self, tool: &ToolSpec, version: Option<&Version>, log: &mut InstallLog) -> Result<PackageManager> {
+        let mut errors = Vec::new();
+        for (manager, package) in self.candidates(tool) {
+            let args = match version {
+                Some(version) => match manager.pinned_install_args(package, version) {
+                    Some(args) => args,
+                    None => {
+                        log.step(format!("{} cannot install {} {}", manager, package, version));
+                        continue;
+                    }
+                },
+                None => manager.args(PackageAction::Install, package),
+            };
+
+            match self.run(manager, PackageAction::Install, package, args, log).await {
+                Ok(()) => return Ok(manager),
+                Err(e) => errors.push(format!("{:#}", e)),
+            }
+        }
+
+        if errors.is_empty() {
+            match version {
+                Some(version) => bail!("No package manager on PATH can install {} {}", tool.name, version),
+                None => bail!("No package manager on PATH has a package for {}", tool.name),
+            }
+        }
+        bail!("{}", errors.join("; "))
+    }
+
+    /// Upgrade or remove `tool` with the manager that installed it, or the first candidate
+    /// when that cannot be told from where its binary lives
+    pub async fn manage(
+        &self,
+        tool: &ToolSpec,
+        binary: Option<&Path>,
+        action: PackageAction,
+        log: &mut InstallLog,
+    ) -> Result<PackageManager> {
+        let owner = binary.and_then(|binary| self.owner_of(binary));
+        let (manager, package) = self
+            .candidates(tool)
+            .find(|(manager, _)| owner.is_none_or(|owner| owner == *manager))
+            .with_context(|| format!("No package manager on PATH can {} {}", action, tool.name))?;
+
+        self.run(manager, action, package, manager.args(action, package), log).await?;
+        Ok(manager)
+    }
+
+    /// The available manager that installed `binary`, judging by its location
+    fn owner_of(&self, binary: &Path) -> Option<PackageManager> {
+        let path = binary.to_string_lossy();
+        let system = || self.available().find(|manager| manager.needs_root());
+
+        if path.contains("/.cargo/bin/") {
+            self.available()
+                .find(|manager| matches!(manager, PackageManager::CargoBinstall | PackageManager::Cargo))
+        } else if path.starts_with("/nix/") || path.contains("/.nix-profile/") {
+            Some(PackageManager::Nix).filter(|nix| self.available().any(|manager| manager == *nix))
+        } else if path.contains("/homebrew/") || path.contains("/Cellar/") || path.contains("/linuxbrew/") {
+            Some(PackageManager::Brew).filter(|brew| self.available().any(|manager| manager == *brew))
+        } else if path.starts_with("/usr/bin/") || path.starts_with("/bin/") {
+            system()
+        } else {
+            None
+        }
+    }
+
+    /// Remove `package` with exactly `manager`, e.g. the one recorded when it was installed
+    pub async fn uninstall_with(&self, manager: PackageManager, package: &str, log: &mut InstallLog) -> Result<()> {
+        let args = manager.args(PackageAction::Uninstall, package);
+        self.run(manager, PackageAction::Uninstall, package, args, log).await
+    }
+
+    async fn run(
+        &self,
+        manager: PackageManager,
+        action: PackageAction,
+        package: &str,
+        args: Vec<String>,
+        log: &mut InstallLog,
+    ) -> Result<()> {
+        let program = match (manager, action) {
+            (PackageManager::CargoBinstall, PackageAction::Uninstall) => {
+                let cwd = std::env::current_dir()?;
+                which::which_in("cargo", self.search_path.clone(), cwd).context("cargo is needed to uninstall crates")?
+            }
+            _ => self
+                .available
+                .iter()
+                .find(|(available, _)| *available == manager)
+                .map(|(_, path)| path.clone())
+                .with_context(|| format!("{} is not on PATH", manager))?,
+        };
+
+        let mut command = match &self.sudo {
+            // Never prompt: the progress bars would hide the prompt and the install would hang
+            Some(sudo) if manager.needs_root() => {
+                let mut command = Command::new(sudo);
+                command.arg("-n").arg(&program);
+                command
+            }
+            _ => Command::new(&program),
+        };
+        command.args(&args);
+
+        let _guard = if manager.is_exclusive() {
+            log.step(format!("waiting for {}", manager));
+            Some(self.exclusive.lock().await)
+        } else {
+            None
+        };
+        log.step(format!("{} {} {}", manager, action, package));
+        let output = command
+            .output()
+            .await
+            .with_context(|| format!("Failed to run {}", program.display()))?;
+        log.command(&format!("{} {}", program.display(), args.join(" ")), &output);
+
+        if !output.status.success() {
+            // The whole output is in the log; the last line usually says what went wrong
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if manager.needs_root() && stderr.contains("a password is required") {
+                bail!(
+                    "{} needs sudo, which asked for a password; run `sudo -v` first, or install {} from a prebuilt release or with a user-level manager such as cargo-binstall",
+                    manager,
+                    package
+                );
+            }
+            bail!(
+                "{} {} {} failed: {}",
+                manager,
+                action,
+                package,
+                stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("no output").trim()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// The package managers these tests run are shell scripts
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::tools::catalog::ToolCatalog;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// A script standing in for `program` that records its arguments, failing if `fails`
+    fn fake_manager(dir: &Path, program: &str, fails: bool) {
+        let path = dir.join(program);
+        let log = dir.join("calls.log");
+        let exit = if fails { "echo 'no such package' >&2; exit 1" } else { "exit 0" };
+        fs::write(&path, format!("#!/bin/sh\necho \"{} $*\" >> '{}'\n{}\n", program, log.display(), exit)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn calls(dir: &Path) -> Vec<String> {
+        fs::read_to_string(dir.join("calls.log"))
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_install_uses_distro_package_names_and_falls_back() {
+        let catalog = ToolCatalog::embedded();
+        let fd = catalog.tools.iter().find(|tool| tool.name == "fd").unwrap();
+        let dust = catalog.tools.iter().find(|tool| tool.name == "dust").unwrap();
+
+        let bin = TempDir::new().unwrap();
+        fake_manager(bin.path(), "dnf", true);
+        fake_manager(bin.path(), "cargo", false);
+        fake_manager(bin.path(), "cargo-binstall", false);
+
+        let managers = PackageManagers::detect_in(Some(bin.path().as_os_str().to_owned()));
+        let available: Vec<PackageManager> = managers.available().collect();
+        assert_eq!(available, vec![PackageManager::Dnf, PackageManager::CargoBinstall, PackageManager::Cargo]);
+
+        // dnf fails, so cargo-binstall is preferred over compiling with cargo install
+        let mut log = InstallLog::default();
+        assert_eq!(managers.install(fd, None, &mut log).await.unwrap(), PackageManager::CargoBinstall);
+        assert!(log.to_string().contains("no such package"));
+        assert_eq!(calls(bin.path()), vec!["dnf install -y fd-find", "cargo-binstall --no-confirm fd-find"]);
+
+        let upgraded = managers
+            .manage(dust, Some(Path::new("/home/dev/.cargo/bin/dust")), PackageAction::Upgrade, &mut log)
+            .await;
+        assert_eq!(upgraded.unwrap(), PackageManager::CargoBinstall);
+        let removed = managers
+            .manage(dust, Some(Path::new("/usr/bin/dust")), PackageAction::Uninstall, &mut log)
+            .await;
+        assert!(removed.unwrap_err().to_string().contains("no such package"));
+        assert_eq!(calls(bin.path())[2..], ["cargo-binstall --no-confirm du-dust", "dnf remove -y du-dust"]);
+
+        let nothing = PackageManagers::detect_in(Some(TempDir::new().unwrap().path().as_os_str().to_owned()));
+        assert!(nothing.install(fd, None, &mut log).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sudo_never_prompts_for_a_password() {
+        let catalog = ToolCatalog::embedded();
+        let fd = catalog.tools.iter().find(|tool| tool.name == "fd").unwrap();
+
+        let bin = TempDir::new().unwrap();
+        fake_manager(bin.path(), "apt-get", false);
+        let sudo = bin.path().join("sudo");
+        let script = format!(
+            "#!/bin/sh\necho \"sudo $*\" >> '{}'\necho 'sudo: a password is required' >&2\nexit 1\n",
+            bin.path().join("calls.log").display()
+        );
+        fs::write(&sudo, script).unwrap();
+        fs::set_permissions(&sudo, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let managers = PackageManagers::detect_in(Some(bin.path().as_os_str().to_owned()));
+        let error = managers.install(fd, None, &mut InstallLog::default()).await.unwrap_err();
+
+        assert!(error.to_string().contains("run `sudo -v` first"), "{}", error);
+        let apt = bin.path().join("apt-get");
+        assert_eq!(calls(bin.path()), vec![format!("sudo -n {} install -y fd-find", apt.display())]);
+    }
+}
@@ -0,0 +1,385 @@
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::catalog::{ToolRelease, ToolSpec};
+use super::install_log::InstallLog;
+use crate::utils::fs::{ensure_directory, set_executable};
+
+/// Where prebuilt tools are installed, `~/.local/share/claude-forge/bin` on Linux
+pub fn forge_bin_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .context("Failed to get data directory")?;
+
+    Ok(data_dir.join("claude-forge").join("bin"))
+}
+
+/// Target triple of the platform claude-forge runs on, e.g. `x86_64-unknown-linux-gnu`
+pub fn host_target() -> String {
+    let arch = std::env::consts::ARCH;
+    let vendor_os = match std::env::consts::OS {
+        "macos" => "apple-darwin",
+        "windows" => "pc-windows-msvc",
+        "linux" if cfg!(target_env = "musl") => "unknown-linux-musl",
+        "linux" => "unknown-linux-gnu",
+        other => return format!("{}-unknown-{}", arch, other),
+    };
+
+    format!("{}-{}", arch, vendor_os)
+}
+
+/// A release archive resolved for one platform
+#[derive(Debug, Clone)]
+pub struct ReleaseDownload {
+    pub version: String,
+    pub url: String,
+    /// Binary inside the archive, if the catalog names it
+    pub path: Option<String>,
+    pub bare_binary: bool,
+    pub sha256: String,
+}
+
+impl ReleaseDownload {
+    pub fn for_target(release: &ToolRelease, host: &str) -> Option<Self> {
+        let asset = release.targets.get(host)?;
+        let sha256 = asset.sha256.as_deref()?.to_lowercase();
+        let target = asset.target.as_deref().unwrap_or(host);
+        let expand = |template: &str| template.replace("{version}", &release.version).replace("{target}", target);
+
+        Some(Self {
+            version: release.version.clone(),
+            url: expand(&release.url),
+            path: release.path.as_deref().map(expand),
+            bare_binary: release.bare_binary,
+            sha256,
+        })
+    }
+
+    fn is_tarball(&self) -> bool {
+        self.url.ends_with(".tar.gz") || self.url.ends_with(".tgz")
+    }
+}
+
+/// Installs tools from their release archives into a directory the user owns
+pub struct PrebuiltInstaller {
+    bin_dir: PathBuf,
+    target: String,
+}
+
+impl PrebuiltInstaller {
+    pub fn new(bin_dir: PathBuf) -> Self {
+        Self {
+            bin_dir,
+            target: host_target(),
+        }
+    }
+
+    pub fn bin_dir(&self) -> &Path {
+        &self.bin_dir
+    }
+
+    /// The tool's release archive for this platform, if the catalog has a checksum for it
+    pub fn download_for(&self, tool: &ToolSpec) -> Option<ReleaseDownload> {
+        ReleaseDownload::for_target(tool.release.as_ref()?, &self.target)
+    }
+
+    /// Whether `binary` was placed by this installer
+    pub fn manages(&self, binary: &Path) -> bool {
+        binary.parent() == Some(self.bin_dir.as_path())
+    }
+
+    /// Whether the shell finds binaries in the bin directory without a full path
+    pub fn on_path(&self) -> bool {
+        std::env::var_os("PATH")
+            .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir == self.bin_dir))
+    }
+
+    /// Download, verify and unpack the tool's binary, returning where it was installed
+    pub async fn install(&self, tool: &ToolSpec, log: &mut InstallLog) -> Result<PathBuf> {
+        let download = self
+            .download_for(tool)
+            .with_context(|| format!("The tool catalog has no {} release for {}", tool.name, self.target))?;
+
+        if !download.is_tarball() && !download.bare_binary {
+            bail!(
+                "Unsupported archive format for {}: only .tar.gz archives and bare binaries can be installed",
+                download.url
+            );
+        }
+
+        log.step(format!("downloading {}", download.url));
+        let archive = fetch(&download.url).await?;
+        let checksum = format!("{:x}", Sha256::digest(&archive));
+        if checksum != download.sha256 {
+            bail!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                download.url,
+                download.sha256,
+                checksum
+            );
+        }
+
+        log.step(format!("verified sha256 {}", checksum));
+
+        let binary = if download.bare_binary {
+            archive
+        } else {
+            extract_binary(&archive, &download, &tool.binary)?
+        };
+
+        // Write next to the destination and rename, so a failed install never leaves half a binary
+        ensure_directory(&self.bin_dir)?;
+        let destination = self.bin_dir.join(&tool.binary);
+        let partial = self.bin_dir.join(format!(".{}.partial", tool.binary));
+        fs::write(&partial, binary)
+            .context(format!("Failed to write {}", partial.display()))?;
+        set_executable(&partial)?;
+        fs::rename(&partial, &destination)?;
+        log.step(format!("installed {}", destination.display()));
+
+        Ok(destination)
+    }
+
+    pub fn uninstall(&self, binary: &Path) -> Result<()> {
+        if !self.manages(binary) {
+            bail!("{} was not installed by claude-forge", binary.display());
+        }
+
+        fs::remove_file(binary)
+            .context(format!("Failed to remove {}", binary.display()))
+    }
+}
+
+/// Contents of an `http(s)://` or `file://` URL
+async fn fetch(url: &str) -> Result<Vec<u8>> {
+    if let Some(path) = url.strip_prefix("file://") {
+        return fs::read(path).context(format!("Failed to read {}", path));
+    }
+
+    let client = reqwest::Client::builder()
+        .user_agent("claude-forge")
+        .build()?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .context(format!("Failed to download {}", url))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to download {}: HTTP {}", url, response.status());
+    }
+
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Read just the binary out of a `.tar.gz`; nothing else in the archive touches the disk
+fn extract_binary(archive: &[u8], download: &ReleaseDownload, binary: &str) -> Result<Vec<u8>> {
+    let mut tarball = tar::Archive::new(GzDecoder::new(archive));
+
+    for entry in tarball.entries().context("Failed to read release archive")? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.into_owned();
+        let wanted = match &download.path {
+            Some(wanted) => path == Path::new(wanted),
+            None => path.file_name() == Some(OsStr::new(binary)),
+        };
+
+        if wanted {
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            return Ok(content);
+        }
+    }
+
+    bail!(
+        "{} not found in {}",
+        download.path.as_deref().unwrap_or(binary),
+        download.url
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::catalog::ToolCatalog;
+    use flate2::{write::GzEncoder, Compression};
+    #[cfg(unix)]
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// A release tarball laid out like ripgrep's, and its SHA-256
+    fn release_archive(dir: &Path, target: &str) -> (PathBuf, String) {
+        let script = b"#!/bin/sh\necho 'mytool 1.2.0'\n";
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for name in ["README.md", "mytool"] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(script.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, format!("mytool-1.2.0-{}/{}", target, name), &script[..])
+                .unwrap();
+        }
+
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        let path = dir.join(format!("mytool-1.2.0-{}.tar.gz", target));
+        fs::write(&path, &archive).unwrap();
+        (path, format!("{:x}", Sha256::digest(&archive)))
+    }
+
+    fn catalog_tool(releases: &Path, sha256: &str) -> ToolSpec {
+        let catalog: ToolCatalog = toml::from_str(&format!(
+            r#"
+[[tools]]
+name = "mytool"
+binary = "mytool"
+description = "Test tool"
+
+[tools.release]
+version = "1.2.0"
+url = "file://{}/mytool-{{version}}-{{target}}.tar.gz"
+path = "mytool-{{version}}-{{target}}/mytool"
+
+[tools.release.targets.{}]
+sha256 = "{}"
+"#,
+            releases.display(),
+            host_target(),
+            sha256
+        ))
+        .unwrap();
+        catalog.tools.into_iter().next().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_installs_nothing() {
+        let releases = TempDir::new().unwrap();
+        release_archive(releases.path(), &host_target());
+        let bin = TempDir::new().unwrap();
+        let installer = PrebuiltInstaller::new(bin.path().join("bin"));
+
+        let tampered = catalog_tool(releases.path(), &"0".repeat(64));
+        let error = installer.install(&tampered, &mut InstallLog::default()).await.unwrap_err();
+
+        assert!(error.to_string().contains("Checksum mismatch"));
+        assert!(!installer.bin_dir().exists());
+    }
+
+    #[tokio::test]
+    async fn test_install_extracts_the_verified_binary() {
+        let releases = TempDir::new().unwrap();
+        let (_, sha256) = release_archive(releases.path(), &host_target());
+        let bin = TempDir::new().unwrap();
+        let installer = PrebuiltInstaller::new(bin.path().join("bin"));
+
+        let tool = catalog_tool(releases.path(), &sha256.to_uppercase());
+        let mut log = InstallLog::default();
+        let installed = installer.install(&tool, &mut log).await.unwrap();
+
+        assert!(log.to_string().contains(&format!("verified sha256 {}", sha256)));
+        assert_eq!(installed, bin.path().join("bin/mytool"));
+        assert_eq!(fs::read_to_string(&installed).unwrap(), "#!/bin/sh\necho 'mytool 1.2.0'\n");
+        #[cfg(unix)]
+        assert_eq!(fs::metadata(&installed).unwrap().permissions().mode() & 0o111, 0o111);
+        assert!(installer.manages(&installed));
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_only_removes_managed_binaries() {
+        let releases = TempDir::new().unwrap();
+        let (_, sha256) = release_archive(releases.path(), &host_target());
+        let bin = TempDir::new().unwrap();
+        let installer = PrebuiltInstaller::new(bin.path().join("bin"));
+        let tool = catalog_tool(releases.path(), &sha256);
+        let installed = installer.install(&tool, &mut InstallLog::default()).await.unwrap();
+
+        installer.uninstall(&installed).unwrap();
+
+        assert!(!installed.exists());
+        assert!(installer.uninstall(Path::new("/usr/bin/mytool")).is_err());
+    }
+
+    #[test]
+    fn test_no_download_without_a_release_for_this_target() {
+        let installer = PrebuiltInstaller::new(PathBuf::from("/nonexistent"));
+        let mut tool = catalog_tool(Path::new("/releases"), &"0".repeat(64));
+        assert!(installer.download_for(&tool).is_some());
+
+        tool.release.as_mut().unwrap().targets.clear();
+        assert!(installer.download_for(&tool).is_none());
+    }
+
+    #[test]
+    fn test_default_tools_have_linux_and_macos_releases() {
+        // Tools whose upstream publishes no macOS builds
+        const LINUX_ONLY: [&str; 1] = ["eza"];
+
+        for tool in ToolCatalog::embedded().tools {
+            let release = tool.release.as_ref().unwrap_or_else(|| panic!("{} has no release", tool.name));
+            let mut targets = vec!["x86_64-unknown-linux-gnu", "aarch64-unknown-linux-gnu"];
+            if !LINUX_ONLY.contains(&tool.name.as_str()) {
+                targets.extend(["x86_64-apple-darwin", "aarch64-apple-darwin"]);
+            }
+            for target in targets {
+                assert!(release.targets.contains_key(target), "{} has no {} release", tool.name, target);
+            }
+
+            for host in release.targets.keys() {
+                let mut pinned = release.clone();
+                pinned.targets.get_mut(host).unwrap().sha256 = Some("AB".repeat(32));
+                let download = ReleaseDownload::for_target(&pinned, host).unwrap();
+                assert!(download.url.starts_with("https://github.com/") && download.is_tarball(), "{}", download.url);
+                assert!(!download.url.contains('{'), "{}", download.url);
+                assert_eq!(download.sha256, "ab".repeat(32));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zip_archive_is_rejected() {
+        let releases = TempDir::new().unwrap();
+        let (_, sha256) = release_archive(releases.path(), &host_target());
+        let mut tool = catalog_tool(releases.path(), &sha256);
+        let release = tool.release.as_mut().unwrap();
+        release.url = release.url.replace(".tar.gz", ".zip");
+
+        let installer = PrebuiltInstaller::new(releases.path().join("bin"));
+        let error = installer.install(&tool, &mut InstallLog::default()).await.unwrap_err();
+        assert!(error.to_string().contains("Unsupported archive format"), "{}", error);
+        assert!(!installer.bin_dir().exists());
+    }
+
+    #[tokio::test]
+    async fn test_bare_binary_is_installed_as_is() {
+        let releases = TempDir::new().unwrap();
+        let binary = b"#!/bin/sh\necho 'mytool 1.2.0'\n";
+        fs::write(releases.path().join("mytool"), binary).unwrap();
+        let mut tool = catalog_tool(releases.path(), &format!("{:x}", Sha256::digest(binary)));
+        let release = tool.release.as_mut().unwrap();
+        release.url = format!("file://{}/mytool", releases.path().display());
+        release.bare_binary = true;
+
+        let installer = PrebuiltInstaller::new(releases.path().join("bin"));
+        let installed = installer.install(&tool, &mut InstallLog::default()).await.unwrap();
+        assert_eq!(fs::read(installed).unwrap(), binary);
+    }
+
+    #[test]
+    fn test_unpinned_release_is_not_offered() {
+        let releases = TempDir::new().unwrap();
+        let mut tool = catalog_tool(releases.path(), &"0".repeat(64));
+        tool.release.as_mut().unwrap().targets.get_mut(&host_target()).unwrap().sha256 = None;
+
+        let installer = PrebuiltInstaller::new(releases.path().join("bin"));
+        assert!(installer.download_for(&tool).is_none());
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::installer::InstallMethod;
+use crate::utils::fs::{read_file, write_file};
+
+/// Where claude-forge records the tools it installed, `~/.local/share/claude-forge/state.toml` on Linux
+pub fn forge_state_file() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .context("Failed to get data directory")?;
+
+    Ok(data_dir.join("claude-forge").join("state.toml"))
+}
+
+/// A tool claude-forge installed, and how to take it out again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledTool {
+    pub method: InstallMethod,
+    /// Package the manager installed, for removing it with the same name
+    #[serde(default)]
+    pub package: Option<String>,
+    /// The binary a prebuilt install placed
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    pub installed_at: String,
+}
+
+/// Every tool claude-forge installed, by catalog name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstallState {
+    #[serde(default)]
+    pub tools: BTreeMap<String, InstalledTool>,
+}
+
+impl InstallState {
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        toml::from_str(&read_file(path)?)
+            .context(format!("Invalid {}", path.display()))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        write_file(path, &toml::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::package_manager::PackageManager;
+    use tempfile::TempDir;
+
+    fn state_file(dir: &TempDir) -> PathBuf {
+        dir.path().join("claude-forge/state.toml")
+    }
+
+    #[test]
+    fn test_missing_state_is_empty() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(InstallState::load_from(&state_file(&dir)).unwrap(), InstallState::default());
+    }
+
+    #[test]
+    fn test_state_round_trips_install_methods() {
+        let dir = TempDir::new().unwrap();
+        let path = state_file(&dir);
+
+        let mut state = InstallState::default();
+        state.tools.insert(
+            "fd".to_string(),
+            InstalledTool {
+                method: InstallMethod::Manager(PackageManager::CargoBinstall),
+                package: Some("fd-find".to_string()),
+                path: None,
+                installed_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+        );
+        state.tools.insert(
+            "ripgrep".to_string(),
+            InstalledTool {
+                method: InstallMethod::Prebuilt,
+                package: None,
+                path: Some(PathBuf::from("/home/dev/.local/share/claude-forge/bin/rg")),
+                installed_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+        );
+        state.save_to(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("method = \"cargo-binstall\""));
+        assert!(content.contains("method = \"prebuilt\""));
+        assert_eq!(InstallState::load_from(&path).unwrap(), state);
+    }
+
+    #[test]
+    fn test_unknown_install_method_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = state_file(&dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "[tools.fd]\nmethod = \"pip\"\ninstalled_at = \"now\"\n").unwrap();
+
+        assert!(InstallState::load_from(&path).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A dotted numeric version such as `14.1.1`; pre-release and build suffixes are ignored
+#[derive(Debug, Clone)]
+pub struct Version(Vec<u64>);
+
+impl Version {
+    /// The version in a tool's `--version` output: the capture group of `pattern`,
+    /// or the first dotted number
+    pub fn parse_output(output: &str, pattern: Option<&str>) -> Result<Option<Self>> {
+        let regex = Regex::new(pattern.unwrap_or(r"\d+(?:\.\d+)+"))
+            .context(format!("Invalid version pattern '{}'", pattern.unwrap_or_default()))?;
+
+        let Some(captures) = regex.captures(output) else { return Ok(None) };
+        let found = captures.get(1).or_else(|| captures.get(0)).map_or("", |m| m.as_str());
+        Ok(found.parse().ok())
+    }
+
+    /// Compare only the components `other` spells out, so `14.1.1` matches a bound of `14`
+    fn cmp_prefix(&self, other: &Version) -> Ordering {
+        let prefix = Version(self.0.iter().copied().take(other.0.len()).collect());
+        prefix.cmp(other)
+    }
+}
+
+impl FromStr for Version {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let digits = s.trim().trim_start_matches('v');
+        // Drop `-beta.1`, `+git` and the like
+        let numeric = digits.split(['-', '+', ' ']).next().unwrap_or_default();
+        let parts = numeric
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| anyhow::anyhow!("Invalid version '{}'", s))?;
+
+        Ok(Version(parts))
+    }
+}
+
+impl Ord for Version {
+    /// Missing components count as zero: `14.1` == `14.1.0`
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.0.len().max(other.0.len());
+        let component = |version: &Version, i: usize| version.0.get(i).copied().unwrap_or(0);
+        (0..len)
+            .map(|i| component(self, i).cmp(&component(other, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(u64::to_string).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Versions a tool must be within; both bounds are inclusive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionReq {
+    pub min: Option<Version>,
+    /// Compared on its own components, so a maximum of `14` allows any `14.x`
+    pub max: Option<Version>,
+}
+
+impl VersionReq {
+    /// Exactly `version`, or any release of it when it omits components
+    pub fn exact(version: Version) -> Self {
+        Self {
+            min: Some(version.clone()),
+            max: Some(version),
+        }
+    }
+
+    pub fn is_any(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.min.as_ref().is_none_or(|min| version >= min)
+            && self.max.as_ref().is_none_or(|max| version.cmp_prefix(max).is_le())
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if min == max => write!(f, "= {}", min),
+            (Some(min), Some(max)) => write!(f, ">= {}, <= {}", min, max),
+            (Some(min), None) => write!(f, ">= {}", min),
+            (None, Some(max)) => write!(f, "<= {}", max),
+            (None, None) => write!(f, "any"),
+        }
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = anyhow::Error;
+
+    /// `*`, `>= 1.2`, `<= 2`, `>= 1.2, <= 2`, or a bare `1.7` for that version only
+    fn from_str(s: &str) -> Result<Self> {
+        let mut req = VersionReq::default();
+        if matches!(s.trim(), "" | "*") {
+            return Ok(req);
+        }
+
+        for part in s.split(',').map(str::trim) {
+            if let Some(min) = part.strip_prefix(">=") {
+                req.min = Some(min.parse()?);
+            } else if let Some(max) = part.strip_prefix("<=") {
+                req.max = Some(max.parse()?);
+            } else {
+                let version: Version = part.strip_prefix('=').unwrap_or(part).parse()?;
+                req = VersionReq::exact(version);
+            }
+        }
+
+        Ok(req)
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionReq {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_version_is_read_from_tool_output() {
+        let parse = |output: &str| Version::parse_output(output, None).unwrap();
+        assert_eq!(parse("ripgrep 14.1.1 (rev 4649aa9700)\n\nfeatures:+pcre2"), Some(v("14.1.1")));
+        assert_eq!(parse("eza - A modern, maintainable replacement for ls\nv0.18.2 [+git]"), Some(v("0.18.2")));
+        assert_eq!(parse("no version here"), None);
+    }
+
+    #[test]
+    fn test_version_pattern_picks_the_version() {
+        assert_eq!(
+            Version::parse_output("jq-1.7.1", Some(r"jq-(\d+\.\d+(?:\.\d+)?)")).unwrap(),
+            Some(v("1.7.1"))
+        );
+        assert!(Version::parse_output("x", Some("(")).is_err());
+    }
+
+    #[test]
+    fn test_versions_compare_by_number() {
+        assert_eq!(v("v2.0.0-beta.1"), v("2"));
+        assert!(v("14.1") < v("14.1.1"));
+        assert!("fourteen".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_requirement_bounds_are_inclusive() {
+        let req = VersionReq { min: Some(v("13.0")), max: Some(v("14")) };
+        assert!(req.matches(&v("14.1.1")));
+        assert!(req.matches(&v("13.0.0")));
+        assert!(!req.matches(&v("12.9")));
+        assert!(!req.matches(&v("15.0.0")));
+        assert_eq!(req.to_string(), ">= 13.0, <= 14");
+        assert!(VersionReq::default().matches(&v("0.1")));
+    }
+
+    #[test]
+    fn test_requirement_is_parsed() {
+        let parse_req = |s: &str| s.parse::<VersionReq>().unwrap();
+        assert_eq!(parse_req(">=13.0, <= 14"), VersionReq { min: Some(v("13.0")), max: Some(v("14")) });
+        assert_eq!(parse_req("*"), VersionReq::default());
+        assert_eq!(parse_req("1.7").to_string(), "= 1.7");
+        assert!(parse_req("1.7").matches(&v("1.7.1")));
+        assert!(!parse_req("1.7").matches(&v("1.8")));
+    }
+
+    #[test]
+    fn test_unsupported_requirement_is_rejected() {
+        assert!("~> 1.7".parse::<VersionReq>().is_err());
+    }
+}
@@ -0,0 +1,9 @@
+use similar::TextDiff;
+
+/// Unified diff of two texts, empty if they are equal
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(old_name, new_name)
+        .to_string()
+}
@@ -1,5 +1,5 @@
-use anyhow::{Context, Result};
-use std::path::Path;
+use anyhow::{bail, Context, Result};
+use std::path::{Component, Path};
 
 pub fn ensure_directory(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -24,3 +24,30 @@ pub fn read_file(path: &Path) -> Result<String> {
     std::fs::read_to_string(path)
         .context(format!("Failed to read file: {}", path.display()))
 }
+
+/// Fails unless `path` stays inside the directory it is joined to: no root, prefix or `..`
+pub fn ensure_relative(path: &str) -> Result<()> {
+    let escapes = Path::new(path)
+        .components()
+        .any(|component| matches!(component, Component::RootDir | Component::Prefix(_) | Component::ParentDir));
+    if escapes {
+        bail!("Path '{}' must be relative and must not contain '..'", path);
+    }
+    Ok(())
+}
+
+pub fn set_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(path, perms)
+            .context(format!("Failed to set permissions: {}", path.display()))?;
+    }
+
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+#!/bin/bash
+# Run Credo on edited Elixir files when the project uses it
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.(ex|exs)$'; then
+  if command -v mix &> /dev/null && grep -q ":credo" mix.exs 2>/dev/null; then
+    if ! mix credo "$file_path" >&2; then
+      echo "⚠️  Credo issues found in $file_path" >&2
+    fi
+  fi
+fi
+
+exit 0
@@ -0,0 +1,15 @@
+#!/bin/bash
+# Format Elixir files after editing
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.(ex|exs|heex)$'; then
+  if command -v mix &> /dev/null; then
+    mix format "$file_path" 2>/dev/null || true
+  fi
+fi
+
+exit 0
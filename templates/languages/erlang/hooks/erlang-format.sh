@@ -0,0 +1,15 @@
+#!/bin/bash
+# Format Erlang files after editing
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.(erl|hrl)$'; then
+  if command -v erlfmt &> /dev/null; then
+    erlfmt --write "$file_path" 2>/dev/null || true
+  fi
+fi
+
+exit 0
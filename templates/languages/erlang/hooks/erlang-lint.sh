@@ -0,0 +1,17 @@
+#!/bin/bash
+# Run Elvis on edited Erlang files when the project configures it
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.(erl|hrl)$'; then
+  if [ -f elvis.config ] && command -v elvis &> /dev/null; then
+    if ! elvis rock "$file_path" >&2; then
+      echo "⚠️  Elvis issues found in $file_path" >&2
+    fi
+  fi
+fi
+
+exit 0
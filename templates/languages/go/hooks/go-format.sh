@@ -0,0 +1,17 @@
+#!/bin/bash
+# Format Go files after editing
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.go$'; then
+  if command -v goimports &> /dev/null; then
+    goimports -w "$file_path" 2>/dev/null || true
+  elif command -v gofmt &> /dev/null; then
+    gofmt -w "$file_path" 2>/dev/null || true
+  fi
+fi
+
+exit 0
@@ -0,0 +1,17 @@
+#!/bin/bash
+# Vet the package of an edited Go file
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.go$'; then
+  if command -v go &> /dev/null; then
+    if ! (cd "$(dirname "$file_path")" && go vet . 2>&1); then
+      echo "⚠️  go vet issues found in $(dirname "$file_path")" >&2
+    fi
+  fi
+fi
+
+exit 0
@@ -0,0 +1,15 @@
+#!/bin/bash
+# Format Java files after editing
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.java$'; then
+  if command -v google-java-format &> /dev/null; then
+    google-java-format --replace "$file_path" 2>/dev/null || true
+  fi
+fi
+
+exit 0
@@ -0,0 +1,25 @@
+#!/bin/bash
+# Run Checkstyle on edited Java files when the project configures it
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.java$'; then
+  config=""
+  for candidate in checkstyle.xml config/checkstyle/checkstyle.xml; do
+    if [ -f "$candidate" ]; then
+      config="$candidate"
+      break
+    fi
+  done
+
+  if [ -n "$config" ] && command -v checkstyle &> /dev/null; then
+    if ! checkstyle -c "$config" "$file_path" >&2; then
+      echo "⚠️  Checkstyle issues found in $file_path" >&2
+    fi
+  fi
+fi
+
+exit 0
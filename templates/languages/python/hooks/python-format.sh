@@ -0,0 +1,17 @@
+#!/bin/bash
+# Format Python files after editing
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.pyi?$'; then
+  if command -v ruff &> /dev/null; then
+    ruff format "$file_path" 2>/dev/null || true
+  elif command -v black &> /dev/null; then
+    black -q "$file_path" 2>/dev/null || true
+  fi
+fi
+
+exit 0
@@ -0,0 +1,20 @@
+#!/bin/bash
+# Lint Python files after editing
+
+set -euo pipefail
+
+input=$(cat)
+file_path=$(echo "$input" | jq -r '.tool_input.file_path // .tool_input.file // ""')
+
+if echo "$file_path" | grep -qE '\.pyi?$'; then
+  if command -v ruff &> /dev/null; then
+    if ! ruff check "$file_path" 2>&1; then
+      echo "⚠️  Ruff issues found in $file_path" >&2
+      echo "Run /lint-fix to automatically fix issues" >&2
+    fi
+  elif command -v flake8 &> /dev/null; then
+    flake8 "$file_path" >&2 || echo "⚠️  flake8 issues found in $file_path" >&2
+  fi
+fi
+
+exit 0
@@ -0,0 +1,12 @@
+#!/bin/bash
+# Custom hook
+
+set -euo pipefail
+
+# Read JSON input from stdin
+input=$(cat)
+
+# Process input as needed
+echo "$input" | jq .
+
+exit 0